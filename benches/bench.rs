@@ -18,52 +18,107 @@ use adventofcode_2025::common::day_input_filename;
 use adventofcode_2025::common::get_file_lines;
 use adventofcode_2025::common::Solution;
 use adventofcode_2025::days;
+use adventofcode_2025::generate;
+use criterion::AxisScale;
+use criterion::BenchmarkId;
+use criterion::PlotConfiguration;
+use criterion::Throughput;
 
-macro_rules! setup_benchmark {
-    ($($day_name:ident),*) => {
-        $(
-            pub fn $day_name(c: &mut criterion::Criterion) {
-                let day_name = stringify!($day_name);
-                let day_num: u8 = day_name[3..].parse().unwrap();
-                c.bench_function(&format!("Day {}", day_num), |bencher| {
-                    let input_lines = get_file_lines(&day_input_filename(day_num)).unwrap();
-                    bencher.iter(|| days::$day_name::solve(&input_lines));
-                });
+/// Generator sizes for the scaling benchmarks, up to 10000. Days 3 and 4 generate inputs quadratic
+/// in size. Day 9 generates about 25000 red tiles at size 10000, too many for part B, which checks
+/// every pair of red tiles against every edge of the loop. Day 8 takes about ten seconds per
+/// iteration at size 10000.
+fn scaling_sizes(day: u8) -> &'static [usize] {
+    match day {
+        3 | 4 | 9 => &[10, 100, 1000],
+        _ => &[100, 1000, 10000],
+    }
+}
+
+/// The days whose input is present, with their input lines.
+fn inputs() -> Vec<(u8, Vec<String>)> {
+    days::all_numbers()
+        .into_iter()
+        .filter_map(|day| match get_file_lines(&day_input_filename(day)) {
+            Ok(lines) => Some((day, lines)),
+            Err(e) => {
+                eprintln!("Skipping day {}: {}", day, e);
+                None
             }
-        )*
+        })
+        .collect()
+}
+
+pub fn days(c: &mut criterion::Criterion) {
+    for (day, input_lines) in inputs() {
+        let solve = days::get_solver(day).unwrap();
+        let mut group = c.benchmark_group(format!("Day {}", day));
+        group.bench_function("solve", |bencher| bencher.iter(|| solve(&input_lines)));
+        days::get_phases(day).unwrap()(&input_lines, &mut |name, phase| {
+            group.bench_function(name, |bencher| bencher.iter(&mut *phase));
+        });
+        group.finish();
+    }
+}
 
-        pub fn days_all(c: &mut criterion::Criterion) {
-            let solvers_and_inputs: Vec<(fn(&[String]) -> Solution, Vec<String>)> = days::all_numbers()
-                .into_iter()
-                .map(|day| {
-                    (
-                        days::get_solver(day).unwrap(),
-                        get_file_lines(&day_input_filename(day)).unwrap(),
-                    )
-                })
-                .collect();
+pub fn days_all(c: &mut criterion::Criterion) {
+    let solvers_and_inputs: Vec<(days::Solver, Vec<String>)> = inputs()
+        .into_iter()
+        .map(|(day, input_lines)| (days::get_solver(day).unwrap(), input_lines))
+        .collect();
 
-            c.bench_function("All days", |bencher| {
-                bencher.iter(|| {
-                    solvers_and_inputs
-                        .iter()
-                        .map(|(solver, input)| solver(&input))
-                        .collect::<Vec<Solution>>()
-                })
-            });
+    c.bench_function("All days", |bencher| {
+        bencher.iter(|| {
+            solvers_and_inputs
+                .iter()
+                .map(|(solver, input)| solver(input))
+                .collect::<Vec<Solution>>()
+        })
+    });
+}
+
+pub fn variants(c: &mut criterion::Criterion) {
+    for (day, input_lines) in inputs() {
+        let variants = days::get_variants(day).unwrap();
+        if variants.len() > 1 {
+            let mut group = c.benchmark_group(format!("Day {} variants", day));
+            for (name, solve) in variants {
+                group.bench_function(name, |bencher| bencher.iter(|| solve(&input_lines)));
+            }
+            group.finish();
         }
+    }
+}
 
-        criterion::criterion_group! {
-            name = benches;
-            config = criterion::Criterion::default()
-                .significance_level(0.01)
-                .noise_threshold(0.05)
-                .warm_up_time(::std::time::Duration::from_millis(100))
-                .measurement_time(::std::time::Duration::from_millis(400));
-            targets = $($day_name,)* days_all
+pub fn scaling(c: &mut criterion::Criterion) {
+    for day in days::all_numbers() {
+        let sizes = scaling_sizes(day);
+        let solve = days::get_solver(day).unwrap();
+        let mut group = c.benchmark_group(format!("Day {} scaling", day));
+        group
+            .sample_size(10)
+            .plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
+        for size in sizes {
+            let input_lines = generate::generate(day, 0, *size).unwrap();
+            let bytes: usize = input_lines.iter().map(|line| line.len() + 1).sum();
+            group.throughput(Throughput::Bytes(u64::try_from(bytes).unwrap()));
+            group.bench_with_input(
+                BenchmarkId::from_parameter(size),
+                &input_lines,
+                |bencher, input_lines| bencher.iter(|| solve(input_lines)),
+            );
         }
-        criterion::criterion_main!(benches);
-    };
+        group.finish();
+    }
 }
 
-setup_benchmark!(day01, day02, day03, day04, day05, day06, day07, day08, day09, day12);
+criterion::criterion_group! {
+    name = benches;
+    config = criterion::Criterion::default()
+        .significance_level(0.01)
+        .noise_threshold(0.05)
+        .warm_up_time(::std::time::Duration::from_millis(100))
+        .measurement_time(::std::time::Duration::from_millis(400));
+    targets = days, days_all, variants, scaling
+}
+criterion::criterion_main!(benches);
@@ -0,0 +1,56 @@
+// Solutions to Advent of Code 2025
+// Copyright (C) 2025  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-changed=inputs");
+
+    // The puzzle inputs are in a private submodule, so tests that need them are ignored unless
+    // it is checked out.
+    println!("cargo:rustc-check-cfg=cfg(inputs)");
+    if std::fs::read_dir("inputs").is_ok_and(|mut entries| entries.next().is_some()) {
+        println!("cargo:rustc-cfg=inputs");
+    }
+
+    #[cfg(feature = "ffi")]
+    {
+        // Only the C API goes in the header, so parse src/ffi.rs alone rather than the whole
+        // crate. The build writes to OUT_DIR and only warns if the committed header is stale.
+        let crate_dir = std::path::PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+        let out_dir = std::path::PathBuf::from(std::env::var("OUT_DIR").unwrap());
+        let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml"))
+            .expect("Failed to read cbindgen.toml");
+        let header = out_dir.join("adventofcode_2025.h");
+        cbindgen::Builder::new()
+            .with_config(config)
+            .with_src(crate_dir.join("src/ffi.rs"))
+            .generate()
+            .expect("Failed to generate C header")
+            .write_to_file(&header);
+
+        let committed = crate_dir.join("ffi/adventofcode_2025.h");
+        if std::fs::read(&header).ok() != std::fs::read(&committed).ok() {
+            println!(
+                "cargo:warning={} is out of date; regenerate it with `cp {} {}`",
+                committed.display(),
+                header.display(),
+                committed.display(),
+            );
+        }
+    }
+}
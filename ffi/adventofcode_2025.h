@@ -0,0 +1,60 @@
+#ifndef ADVENTOFCODE_2025_H
+#define ADVENTOFCODE_2025_H
+
+/* Generated by cbindgen from src/ffi.rs with `--features ffi`. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Result codes returned by the `aoc_*` functions.
+ */
+typedef enum AocStatus {
+  AOC_STATUS_OK = 0,
+  AOC_STATUS_UNKNOWN_DAY = 1,
+  AOC_STATUS_INVALID_INPUT = 2,
+  AOC_STATUS_PANIC = 3,
+} AocStatus;
+
+/**
+ * Solve `day` for the `len` bytes of UTF-8 input at `input`.
+ *
+ * On success, `*out_a` and `*out_b` are set to NUL-terminated answers which the caller must
+ * release with [`aoc_free_string`]. On failure they are set to `NULL`, and the status is
+ * `InvalidInput` if the input is not UTF-8 or does not parse, and `Panic` only if the solver
+ * panics on input that parses.
+ *
+ * # Safety
+ *
+ * `input` must point to `len` readable bytes, and `out_a` and `out_b` must be valid for writes.
+ */
+enum AocStatus aoc_solve(uint8_t day, const uint8_t *input, size_t len, char **out_a, char **out_b);
+
+/**
+ * Release a string returned by [`aoc_solve`]. Passing `NULL` is a no-op.
+ *
+ * # Safety
+ *
+ * `s` must be `NULL` or a pointer returned by [`aoc_solve`] that has not already been freed.
+ */
+void aoc_free_string(char *s);
+
+/**
+ * Write up to `cap` registered day numbers to `out` and return the total number of registered
+ * days. Call with `cap == 0` to query the required capacity.
+ *
+ * # Safety
+ *
+ * `out` must be valid for `cap` writes.
+ */
+size_t aoc_days(uint8_t *out, size_t cap);
+
+/**
+ * The crate version as a static NUL-terminated string.
+ */
+const char *aoc_version(void);
+
+#endif  /* ADVENTOFCODE_2025_H */
@@ -0,0 +1,102 @@
+/*
+ * Solutions to Advent of Code 2025
+ * Copyright (C) 2025  Emil Lundberg <emil@emlun.se>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+#include <stdio.h>
+#include <string.h>
+
+#include "adventofcode_2025.h"
+
+static int failures = 0;
+
+#define CHECK(cond)                                                   \
+  do {                                                                \
+    if (!(cond)) {                                                    \
+      fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, \
+              #cond);                                                 \
+      failures++;                                                     \
+    }                                                                 \
+  } while (0)
+
+static void test_version(void) {
+  const char *version = aoc_version();
+  CHECK(version != NULL);
+  CHECK(strlen(version) > 0);
+}
+
+static void test_days(void) {
+  size_t n = aoc_days(NULL, 0);
+  CHECK(n >= 12);
+
+  uint8_t days[32];
+  CHECK(aoc_days(days, sizeof days) == n);
+  CHECK(days[0] == 1);
+  for (size_t i = 1; i < n; i++) {
+    CHECK(days[i] > days[i - 1]);
+  }
+}
+
+static void test_solve(void) {
+  const char *input = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+  char *a = NULL;
+  char *b = NULL;
+
+  CHECK(aoc_solve(1, (const uint8_t *)input, strlen(input), &a, &b) ==
+        AOC_STATUS_OK);
+  CHECK(a != NULL && strcmp(a, "3") == 0);
+  CHECK(b != NULL && strcmp(b, "6") == 0);
+
+  aoc_free_string(a);
+  aoc_free_string(b);
+}
+
+static void test_errors(void) {
+  char *a = NULL;
+  char *b = NULL;
+
+  CHECK(aoc_solve(26, (const uint8_t *)"", 0, &a, &b) ==
+        AOC_STATUS_UNKNOWN_DAY);
+  CHECK(a == NULL && b == NULL);
+
+  const uint8_t not_utf8[] = {0xff, 0xfe, '\n'};
+  CHECK(aoc_solve(1, not_utf8, sizeof not_utf8, &a, &b) ==
+        AOC_STATUS_INVALID_INPUT);
+  CHECK(a == NULL && b == NULL);
+
+  CHECK(aoc_solve(1, NULL, 1, &a, &b) == AOC_STATUS_INVALID_INPUT);
+
+  const char *malformed = "L68\nX30\n";
+  CHECK(aoc_solve(1, (const uint8_t *)malformed, strlen(malformed), &a, &b) ==
+        AOC_STATUS_INVALID_INPUT);
+  CHECK(a == NULL && b == NULL);
+
+  aoc_free_string(NULL);
+}
+
+int main(void) {
+  test_version();
+  test_days();
+  test_solve();
+  test_errors();
+
+  if (failures > 0) {
+    fprintf(stderr, "%d check(s) failed\n", failures);
+    return 1;
+  }
+  printf("All C API checks passed\n");
+  return 0;
+}
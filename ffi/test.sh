@@ -0,0 +1,11 @@
+#!/bin/sh
+# Build the cdylib with the C API and run the C test program against it.
+
+set -e
+
+cd "$(dirname "$0")/.."
+
+cargo rustc --lib --release --features ffi --crate-type cdylib
+mkdir -p target/ffi
+cc -std=c99 -Wall -Wextra -Werror -Iffi -o target/ffi/test ffi/test.c -Ltarget/release -ladventofcode_2025
+LD_LIBRARY_PATH=target/release target/ffi/test
@@ -0,0 +1,111 @@
+// Solutions to Advent of Code 2025
+// Copyright (C) 2025  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Terminal animations of solver progress, drawn on standard error so that the answers on
+//! standard output stay intact.
+
+use std::fmt::Display;
+use std::io::Write;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+const CLEAR_SCREEN: &str = "\x1b[2J";
+const CURSOR_HOME: &str = "\x1b[H";
+const CLEAR_TO_END: &str = "\x1b[J";
+const HIDE_CURSOR: &str = "\x1b[?25l";
+const SHOW_CURSOR: &str = "\x1b[?25h";
+
+static FRAME_DELAY_MICROS: AtomicU64 = AtomicU64::new(50_000);
+
+/// Set the pause after each frame of every subsequent animation.
+pub fn set_frame_delay(delay: Duration) {
+    FRAME_DELAY_MICROS.store(
+        u64::try_from(delay.as_micros()).unwrap_or(u64::MAX),
+        Ordering::Relaxed,
+    );
+}
+
+pub fn frame_delay() -> Duration {
+    Duration::from_micros(FRAME_DELAY_MICROS.load(Ordering::Relaxed))
+}
+
+/// `text` in the given colour of the 256-colour palette.
+pub fn paint(color: u8, text: impl Display) -> String {
+    format!("\x1b[38;5;{color}m{text}\x1b[0m")
+}
+
+/// A colour of the 256-colour palette, cycling through the hues as `i` increases.
+pub fn hue(i: usize) -> u8 {
+    let step = (i % 30) as u8;
+    let t = step % 5;
+    let (r, g, b) = match step / 5 {
+        0 => (5, t, 0),
+        1 => (5 - t, 5, 0),
+        2 => (0, 5, t),
+        3 => (0, 5 - t, 5),
+        4 => (t, 0, 5),
+        _ => (5, 0, 5 - t),
+    };
+    16 + 36 * r + 6 * g + b
+}
+
+/// Colours of the 256-colour palette from cold to hot.
+const HEAT: [u8; 19] = [
+    21, 27, 33, 39, 45, 51, 50, 48, 46, 82, 118, 154, 190, 226, 220, 214, 208, 202, 196,
+];
+
+/// A colour of the 256-colour palette from blue for 0.0 to red for 1.0.
+pub fn heat(fraction: f64) -> u8 {
+    let i = (fraction.clamp(0.0, 1.0) * (HEAT.len() - 1) as f64).round() as usize;
+    HEAT[i]
+}
+
+/// A sequence of frames redrawn in place. The cursor is hidden while the animation is alive, and
+/// the last frame is left on screen when it is dropped.
+pub struct Animation {
+    out: std::io::Stderr,
+}
+
+impl Animation {
+    pub fn new() -> Self {
+        let out = std::io::stderr();
+        let _ = write!(out.lock(), "{CLEAR_SCREEN}{HIDE_CURSOR}");
+        Self { out }
+    }
+
+    /// Replace the previous frame with `frame`, then pause for the [frame delay](frame_delay).
+    pub fn frame(&mut self, frame: &str) {
+        {
+            let mut out = self.out.lock();
+            let _ = write!(out, "{CURSOR_HOME}{frame}{CLEAR_TO_END}");
+            let _ = out.flush();
+        }
+        std::thread::sleep(frame_delay());
+    }
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Animation {
+    fn drop(&mut self) {
+        let _ = writeln!(self.out.lock(), "{SHOW_CURSOR}");
+    }
+}
@@ -14,23 +14,220 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use std::fmt::Display;
 use std::fs::File;
 use std::io::Read;
 use std::path::Path;
 use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
 
 pub type Solution = (String, String);
 
+/// Print intermediate solver state to standard error if the `print` feature is enabled. Otherwise
+/// the arguments are never evaluated and the call compiles to nothing.
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if cfg!(feature = "print") {
+            eprintln!($($arg)*);
+        }
+    };
+}
+pub(crate) use trace;
+
+/// Malformed puzzle input, found on the given 1-based line number or in the input as a whole.
+#[derive(Debug, Eq, PartialEq)]
+pub struct ParseError {
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line: Some(line),
+            message: message.into(),
+        }
+    }
+
+    pub fn input(message: impl Into<String>) -> Self {
+        Self {
+            line: None,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "Line {line}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Any failure of the command line tool or the test harnesses, each with its own exit code.
+#[derive(Debug)]
+pub enum AocError {
+    Io(std::io::Error),
+    Parse(ParseError),
+    /// A day number that is not a number, or a day without a solver.
+    UnknownDay(String),
+    UnknownVariant {
+        day: u8,
+        variant: String,
+    },
+    /// A day that does not support what was asked of it, such as rendering a picture.
+    Unsupported {
+        day: u8,
+        what: &'static str,
+    },
+    /// A command line argument whose value could not be used.
+    InvalidArgument {
+        name: &'static str,
+        value: String,
+    },
+    Unsolved {
+        day: u8,
+        part: char,
+    },
+    Timeout {
+        day: u8,
+        limit: Duration,
+    },
+    WrongAnswer {
+        day: u8,
+        part: char,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl AocError {
+    /// The process exit code for this error. 1 is left for other failures, and Rust uses 101 for
+    /// panics.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Self::Io(_) => 2,
+            Self::Parse(_) => 3,
+            Self::UnknownDay(_) | Self::UnknownVariant { .. } | Self::Unsupported { .. } => 4,
+            Self::Unsolved { .. } => 5,
+            Self::Timeout { .. } => 6,
+            Self::WrongAnswer { .. } => 7,
+            Self::InvalidArgument { .. } => 8,
+        }
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Parse(e) => write!(f, "Invalid input: {e}"),
+            Self::UnknownDay(day) => write!(f, "Unknown day: {day}"),
+            Self::UnknownVariant { day, variant } => {
+                write!(f, "Unknown variant for day {day}: {variant}")
+            }
+            Self::Unsupported { day, what } => write!(f, "Day {day} has no {what}"),
+            Self::InvalidArgument { name, value } => {
+                write!(f, "Invalid value for --{name}: {value:?}")
+            }
+            Self::Unsolved { day, part } => write!(f, "Day {day}{part} is not solved yet"),
+            Self::Timeout { day, limit } => {
+                write!(f, "Day {day} did not finish within {limit:?}")
+            }
+            Self::WrongAnswer {
+                day,
+                part,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Wrong answer for day {day}{part}: expected {expected:?}, got {actual:?}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AocError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for AocError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<ParseError> for AocError {
+    fn from(e: ParseError) -> Self {
+        Self::Parse(e)
+    }
+}
+
+/// Compare the answers to `day` against the expected ones. An expected answer that is empty
+/// accepts anything, and a nonempty one is not met by an empty answer.
+pub fn check_solution(
+    day: u8,
+    (a, b): &Solution,
+    expected_a: &str,
+    expected_b: &str,
+) -> Result<(), AocError> {
+    for (part, actual, expected) in [('a', a, expected_a), ('b', b, expected_b)] {
+        if expected.is_empty() || actual == expected {
+            continue;
+        } else if actual.is_empty() {
+            return Err(AocError::Unsolved { day, part });
+        } else {
+            return Err(AocError::WrongAnswer {
+                day,
+                part,
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// The non-empty lines of `lines`, trimmed and paired with their 1-based line numbers.
+pub fn nonempty_lines(lines: &[String]) -> impl Iterator<Item = (usize, &str)> {
+    lines
+        .iter()
+        .map(|line| line.trim())
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| (i + 1, line))
+}
+
+/// Parse `s` as a number, reporting failure as an error on line `line`.
+pub fn parse_number<T: FromStr>(line: usize, s: &str) -> Result<T, ParseError> {
+    s.parse()
+        .map_err(|_| ParseError::new(line, format!("Invalid number: {s:?}")))
+}
+
 pub fn day_input_filename(day: u8) -> PathBuf {
     let padded_day = format!("{day:02}");
     Path::new("inputs").join(format!("day{padded_day}.in"))
 }
 
-pub fn get_file_lines(path: &Path) -> Result<Vec<String>, std::io::Error> {
+pub fn get_file_lines(path: &Path) -> Result<Vec<String>, AocError> {
     if path == Path::new("-") {
-        read_lines(std::io::stdin())
+        Ok(read_lines(std::io::stdin())?)
     } else {
-        read_lines(File::open(path).unwrap_or_else(|_| panic!("Input file not found: {path:?}")))
+        let file = File::open(path).map_err(|e| {
+            std::io::Error::new(e.kind(), format!("Input file not found: {path:?}: {e}"))
+        })?;
+        Ok(read_lines(file)?)
     }
 }
 
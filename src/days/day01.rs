@@ -14,46 +14,99 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use crate::common::nonempty_lines;
+use crate::common::parse_number;
+use crate::common::ParseError;
 use crate::common::Solution;
 
-fn solve_a(moves: &[i32]) -> usize {
-    moves
-        .iter()
-        .scan(50, |pos, step| {
-            *pos = (*pos + step).rem_euclid(100);
-            Some(*pos)
-        })
-        .filter(|pos| *pos == 0)
-        .count()
+/// The dial position after each rotation, and the number of times the rotation points the dial at
+/// 0.
+fn rotate(moves: &[i32]) -> impl Iterator<Item = (i32, i32)> + '_ {
+    moves.iter().scan(50, |pos, step| {
+        let zero_dist = if step.is_positive() {
+            100 - *pos
+        } else if *pos == 0 {
+            100
+        } else {
+            *pos
+        };
+        let zeros = if step.abs() >= zero_dist {
+            1 + (step.abs() - zero_dist) / 100
+        } else {
+            0
+        };
+        *pos = (*pos + step).rem_euclid(100);
+        Some((*pos, zeros))
+    })
+}
+
+pub fn solve_a(moves: &[i32]) -> usize {
+    rotate(moves).filter(|(pos, _)| *pos == 0).count()
 }
 
-fn solve_b(moves: &[i32]) -> i32 {
-    moves
-        .iter()
-        .scan(50, |pos, step| {
-            let zero_dist = if step.is_positive() {
-                100 - *pos
-            } else if *pos == 0 {
-                100
+pub fn solve_b(moves: &[i32]) -> i32 {
+    rotate(moves).map(|(_, zeros)| zeros).sum()
+}
+
+pub fn parse(lines: &[String]) -> Result<Vec<i32>, ParseError> {
+    nonempty_lines(lines)
+        .map(|(l, line)| {
+            if let Some(clicks) = line.strip_prefix('R') {
+                parse_number::<u16>(l, clicks).map(i32::from)
+            } else if let Some(clicks) = line.strip_prefix('L') {
+                parse_number::<u16>(l, clicks).map(|clicks| -i32::from(clicks))
             } else {
-                *pos
-            };
-            let zeros =
-                if step.abs() >= zero_dist { 1 } else { 0 } + (step.abs() - zero_dist) / 100;
-            *pos = (*pos + step).rem_euclid(100);
-            Some(zeros)
+                Err(ParseError::new(l, format!("Invalid rotation: {line:?}")))
+            }
         })
-        .sum()
+        .collect()
 }
 
 pub fn solve(lines: &[String]) -> Solution {
-    let moves: Vec<i32> = lines
-        .iter()
-        .map(|line| {
-            let sign = if line[0..1] == *"R" { 1 } else { -1 };
-            sign * line[1..].parse::<i32>().unwrap()
-        })
-        .collect();
+    let moves = parse(lines).unwrap_or_else(|e| panic!("{e}"));
 
     (solve_a(&moves).to_string(), solve_b(&moves).to_string())
 }
+
+/// The rotations that leave the dial at 0 or point it at 0 on the way.
+pub fn explain(lines: &[String]) -> Vec<String> {
+    let moves = parse(lines).unwrap_or_else(|e| panic!("{e}"));
+    rotate(&moves)
+        .zip(&moves)
+        .enumerate()
+        .filter(|(_, ((pos, zeros), _))| *pos == 0 || *zeros > 0)
+        .map(|(i, ((pos, zeros), step))| {
+            let direction = if *step < 0 { 'L' } else { 'R' };
+            format!(
+                "Rotation {} ({direction}{}): points at 0 {zeros} times, stops at {pos}",
+                i + 1,
+                step.abs()
+            )
+        })
+        .collect()
+}
+
+/// Reference implementation: turn the dial one click at a time.
+pub fn solve_reference(lines: &[String]) -> Solution {
+    let mut pos: i32 = 50;
+    let mut stops_at_zero = 0;
+    let mut passes_zero = 0;
+    for line in lines {
+        let (direction, clicks) = line.split_at(1);
+        let step = match direction {
+            "R" => 1,
+            "L" => -1,
+            _ => panic!("Invalid direction: {direction}"),
+        };
+        for _ in 0..clicks.parse::<u32>().unwrap() {
+            pos = (pos + step).rem_euclid(100);
+            if pos == 0 {
+                passes_zero += 1;
+            }
+        }
+        if pos == 0 {
+            stops_at_zero += 1;
+        }
+    }
+    (stops_at_zero.to_string(), passes_zero.to_string())
+}
@@ -14,10 +14,18 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use std::collections::BTreeSet;
 use std::collections::HashSet;
 
+use crate::common::nonempty_lines;
+use crate::common::parse_number;
+use crate::common::ParseError;
 use crate::common::Solution;
 
+/// Bound on IDs, as long as those in the puzzle input, so that the sums of the invalid IDs fit in
+/// a `usize`.
+const MAX_ID: u64 = 9_999_999_999;
+
 fn parse_split_n<const N: usize>(s: &str) -> Option<[usize; N]> {
     if s.len().is_multiple_of(N) {
         let l = s.len() / N;
@@ -44,103 +52,165 @@ fn parse_split(s: &str, n: usize) -> Option<Vec<usize>> {
     }
 }
 
-fn solve_a(ranges: &[(&str, &str)]) -> usize {
-    ranges
-        .iter()
-        .filter(|(ls, rs)| ls.len().is_multiple_of(2) || rs.len().is_multiple_of(2))
-        .flat_map(|(ls, rs)| {
-            let min = if let Some([minl, minr]) = parse_split_n(ls) {
-                if minr > minl {
-                    minl + 1
-                } else {
-                    minl
-                }
-            } else {
-                10_usize.pow(u32::try_from(ls.len().div_ceil(2)).unwrap() - 1)
-            };
-            let max = if let Some([maxl, maxr]) = parse_split_n(rs) {
-                if maxr < maxl {
-                    maxl - 1
-                } else {
-                    maxl
-                }
-            } else {
-                10_usize.pow(u32::try_from(rs.len() / 2).unwrap()) - 1
-            };
+/// The IDs in the range `ls..=rs` made of some digits repeated twice.
+fn doubled_ids<'a>(ls: &'a str, rs: &'a str) -> impl Iterator<Item = usize> + 'a {
+    let min = if let Some([minl, minr]) = parse_split_n(ls) {
+        if minr > minl {
+            minl + 1
+        } else {
+            minl
+        }
+    } else {
+        10_usize.pow(u32::try_from(ls.len().div_ceil(2)).unwrap() - 1)
+    };
+    let max = if let Some([maxl, maxr]) = parse_split_n(rs) {
+        if maxr < maxl {
+            maxl - 1
+        } else {
+            maxl
+        }
+    } else {
+        10_usize.pow(u32::try_from(rs.len() / 2).unwrap()) - 1
+    };
 
-            (min..=max).map(move |n| (ls, rs, n))
-        })
-        .map(|(ls, rs, n)| {
+    (ls.len().is_multiple_of(2) || rs.len().is_multiple_of(2))
+        .then_some(min..=max)
+        .into_iter()
+        .flatten()
+        .map(move |n| {
             let log = n.ilog10() + 1;
             let nn = n + 10_usize.pow(log) * n;
             debug_assert!(nn >= ls.parse().unwrap());
             debug_assert!(nn <= rs.parse().unwrap());
             nn
         })
-        .sum()
 }
 
-fn solve_b(ranges: &[(&str, &str)]) -> usize {
+/// The IDs in the range `ls..=rs` made of some digits repeated at least twice, some of them more
+/// than once.
+fn repeated_ids<'a>(ls: &'a str, rs: &'a str) -> impl Iterator<Item = usize> + 'a {
+    (2..=rs.len())
+        .filter(|repeats| ls.len().is_multiple_of(*repeats) || rs.len().is_multiple_of(*repeats))
+        .flat_map(move |repeats| {
+            let min = if let Some(mins) = parse_split(ls, repeats) {
+                mins.iter()
+                    .rev()
+                    .fold(0, |minr, minl| if minr > *minl { minl + 1 } else { *minl })
+            } else {
+                10_usize.pow(u32::try_from(ls.len().div_ceil(repeats)).unwrap() - 1)
+            };
+            let max = if let Some(maxs) = parse_split(rs, repeats) {
+                maxs.iter().rev().fold(
+                    usize::MAX,
+                    |maxr, maxl| {
+                        if maxr < *maxl {
+                            maxl - 1
+                        } else {
+                            *maxl
+                        }
+                    },
+                )
+            } else {
+                10_usize.pow(u32::try_from(rs.len() / repeats).unwrap()) - 1
+            };
+
+            (min..=max).map(move |n| {
+                let log = n.ilog10() + 1;
+                let nn = (0..repeats).fold(0, |nn, i| {
+                    nn + 10_usize.pow(log * u32::try_from(i).unwrap()) * n
+                });
+                debug_assert!(nn >= ls.parse().unwrap());
+                debug_assert!(nn <= rs.parse().unwrap());
+                nn
+            })
+        })
+}
+
+pub fn solve_a(ranges: &[(&str, &str)]) -> usize {
+    ranges.iter().flat_map(|(ls, rs)| doubled_ids(ls, rs)).sum()
+}
+
+pub fn solve_b(ranges: &[(&str, &str)]) -> usize {
     ranges
         .iter()
-        .flat_map(|(ls, rs)| {
-            (2..=rs.len())
-                .filter(|repeats| {
-                    ls.len().is_multiple_of(*repeats) || rs.len().is_multiple_of(*repeats)
-                })
-                .flat_map(move |repeats| {
-                    let min = if let Some(mins) = parse_split(ls, repeats) {
-                        mins.iter().rev().fold(
-                            0,
-                            |minr, minl| {
-                                if minr > *minl {
-                                    minl + 1
-                                } else {
-                                    *minl
-                                }
-                            },
-                        )
-                    } else {
-                        10_usize.pow(u32::try_from(ls.len().div_ceil(repeats)).unwrap() - 1)
-                    };
-                    let max = if let Some(maxs) = parse_split(rs, repeats) {
-                        maxs.iter().rev().fold(usize::MAX, |maxr, maxl| {
-                            if maxr < *maxl {
-                                maxl - 1
-                            } else {
-                                *maxl
-                            }
-                        })
-                    } else {
-                        10_usize.pow(u32::try_from(rs.len() / repeats).unwrap()) - 1
-                    };
-
-                    (min..=max).map(move |n| {
-                        let log = n.ilog10() + 1;
-                        let nn = (0..repeats).fold(0, |nn, i| {
-                            nn + 10_usize.pow(log * u32::try_from(i).unwrap()) * n
-                        });
-                        debug_assert!(nn >= ls.parse().unwrap());
-                        debug_assert!(nn <= rs.parse().unwrap());
-                        nn
-                    })
-                })
-        })
+        .flat_map(|(ls, rs)| repeated_ids(ls, rs))
         .collect::<HashSet<usize>>()
         .into_iter()
         .sum()
 }
 
+/// The ranges as pairs of digit strings, since the solvers work on the IDs' digits.
+pub fn parse(lines: &[String]) -> Result<Vec<(&str, &str)>, ParseError> {
+    nonempty_lines(lines)
+        .flat_map(|(l, line)| line.split(',').map(move |s| (l, s)))
+        .filter(|(_, s)| !s.is_empty())
+        .map(|(l, s)| {
+            let (ls, rs) = s
+                .split_once('-')
+                .ok_or_else(|| ParseError::new(l, format!("Invalid range: {s:?}")))?;
+            for id in [ls, rs] {
+                if id.starts_with('0') || !id.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(ParseError::new(l, format!("Invalid ID: {id:?}")));
+                }
+                if parse_number::<u64>(l, id)? > MAX_ID {
+                    return Err(ParseError::new(l, format!("ID out of range: {id}")));
+                }
+            }
+            Ok((ls, rs))
+        })
+        .collect()
+}
+
 pub fn solve(lines: &[String]) -> Solution {
-    let ranges: Vec<(&str, &str)> = lines
+    let ranges = parse(lines).unwrap_or_else(|e| panic!("{e}"));
+
+    (solve_a(&ranges).to_string(), solve_b(&ranges).to_string())
+}
+
+/// The invalid IDs found in each range for parts A and B.
+pub fn explain(lines: &[String]) -> Vec<String> {
+    let ranges = parse(lines).unwrap_or_else(|e| panic!("{e}"));
+    ranges
+        .iter()
+        .map(|(ls, rs)| {
+            let ids_b: BTreeSet<usize> = repeated_ids(ls, rs).collect();
+            format!(
+                "{ls}-{rs}: A {:?}, B {:?}",
+                doubled_ids(ls, rs).collect::<Vec<_>>(),
+                ids_b
+            )
+        })
+        .collect()
+}
+
+fn is_repeated(id: &str, repeats: usize) -> bool {
+    id.len().is_multiple_of(repeats) && {
+        let l = id.len() / repeats;
+        (1..repeats).all(|i| id[i * l..(i + 1) * l] == id[..l])
+    }
+}
+
+/// Reference implementation: check every ID in every range.
+pub fn solve_reference(lines: &[String]) -> Solution {
+    let ids: Vec<usize> = lines
         .iter()
         .flat_map(|line| line.split(','))
         .filter(|s| !s.is_empty())
-        .map(|s| {
+        .flat_map(|s| {
             let (l, r) = s.split_once('-').unwrap();
-            (l, r)
+            l.parse::<usize>().unwrap()..=r.parse().unwrap()
         })
         .collect();
-
-    (solve_a(&ranges).to_string(), solve_b(&ranges).to_string())
+    let sol_a: usize = ids
+        .iter()
+        .filter(|id| is_repeated(&id.to_string(), 2))
+        .sum();
+    let sol_b: usize = ids
+        .iter()
+        .filter(|id| {
+            let s = id.to_string();
+            (2..=s.len()).any(|repeats| is_repeated(&s, repeats))
+        })
+        .sum();
+    (sol_a.to_string(), sol_b.to_string())
 }
@@ -16,6 +16,8 @@
 
 use std::cmp::Reverse;
 
+use crate::common::nonempty_lines;
+use crate::common::ParseError;
 use crate::common::Solution;
 
 fn select_battery(bank: &[u32], prev_batteries: &[usize], num_batteries: usize) -> usize {
@@ -28,35 +30,124 @@ fn select_battery(bank: &[u32], prev_batteries: &[usize], num_batteries: usize)
     ri
 }
 
-fn solve_b(banks: &[Vec<u32>], num_batteries: usize) -> u64 {
+/// The digits of the largest joltage made of `num_batteries` batteries of `bank`.
+fn select_batteries(bank: &[u32], num_batteries: usize) -> Vec<u32> {
+    (0..num_batteries)
+        .fold(Vec::with_capacity(num_batteries), |mut selected_bis, _| {
+            let bi = select_battery(bank, &selected_bis, num_batteries);
+            selected_bis.push(bi);
+            selected_bis
+        })
+        .into_iter()
+        .map(|bi| bank[bi])
+        .collect()
+}
+
+fn total_joltage(banks: &[Vec<u32>], num_batteries: usize) -> u64 {
     banks
         .iter()
         .map(|bank| {
-            let selected_bis = (0..num_batteries).fold(
-                Vec::with_capacity(num_batteries),
-                |mut selected_bis, _| {
-                    let bi = select_battery(bank, &selected_bis, num_batteries);
-                    selected_bis.push(bi);
-                    selected_bis
-                },
-            );
-            selected_bis
+            select_batteries(bank, num_batteries)
                 .into_iter()
-                .fold(0_u64, |acc, bi| acc * 10 + u64::from(bank[bi]))
+                .fold(0_u64, |acc, b| acc * 10 + u64::from(b))
         })
         .sum()
 }
 
+pub fn solve_a(banks: &[Vec<u32>]) -> u64 {
+    total_joltage(banks, 2)
+}
+
+pub fn solve_b(banks: &[Vec<u32>]) -> u64 {
+    total_joltage(banks, 12)
+}
+
+pub fn parse(lines: &[String]) -> Result<Vec<Vec<u32>>, ParseError> {
+    nonempty_lines(lines)
+        .map(|(l, line)| {
+            let bank = line
+                .chars()
+                .map(|c| {
+                    c.to_digit(10)
+                        .ok_or_else(|| ParseError::new(l, format!("Invalid battery: {c:?}")))
+                })
+                .collect::<Result<Vec<u32>, ParseError>>()?;
+            if bank.len() < 12 {
+                Err(ParseError::new(l, "Expected at least 12 batteries"))
+            } else {
+                Ok(bank)
+            }
+        })
+        .collect()
+}
+
 pub fn solve(lines: &[String]) -> Solution {
+    let banks = parse(lines).unwrap_or_else(|e| panic!("{e}"));
+
+    (solve_a(&banks).to_string(), solve_b(&banks).to_string())
+}
+
+/// The batteries picked in each bank for parts A and B.
+pub fn explain(lines: &[String]) -> Vec<String> {
+    let banks = parse(lines).unwrap_or_else(|e| panic!("{e}"));
+    let digits = |bank: &[u32], n| -> String {
+        select_batteries(bank, n)
+            .into_iter()
+            .map(|b| char::from_digit(b, 10).unwrap())
+            .collect()
+    };
+    banks
+        .iter()
+        .enumerate()
+        .map(|(i, bank)| {
+            format!(
+                "Bank {}: A picks {}, B picks {}",
+                i + 1,
+                digits(bank, 2),
+                digits(bank, 12)
+            )
+        })
+        .collect()
+}
+
+/// The largest number formed by picking `k` digits of `bank` in order, by exhaustive dynamic
+/// programming over (position, digits left to pick).
+fn largest_joltage(bank: &[u32], k: usize) -> Option<u64> {
+    let mut best: Vec<Option<u64>> = vec![None; k + 1];
+    best[0] = Some(0);
+    for digit in bank.iter().rev() {
+        for picked in (1..=k).rev() {
+            if let Some(rest) = best[picked - 1] {
+                let candidate =
+                    u64::from(*digit) * 10_u64.pow(u32::try_from(picked - 1).unwrap()) + rest;
+                best[picked] = std::cmp::max(best[picked], Some(candidate));
+            }
+        }
+    }
+    best[k]
+}
+
+/// Reference implementation: try every pair of batteries for part A, and dynamic programming
+/// over all choices for part B.
+pub fn solve_reference(lines: &[String]) -> Solution {
     let banks: Vec<Vec<u32>> = lines
         .iter()
         .map(|line| line.trim())
         .filter(|line| !line.is_empty())
         .map(|line| line.chars().map(|c| c.to_digit(10).unwrap()).collect())
         .collect();
-
-    (
-        solve_b(&banks, 2).to_string(),
-        solve_b(&banks, 12).to_string(),
-    )
+    let sol_a: u32 = banks
+        .iter()
+        .map(|bank| {
+            (0..bank.len())
+                .flat_map(|i| ((i + 1)..bank.len()).map(move |j| bank[i] * 10 + bank[j]))
+                .max()
+                .unwrap()
+        })
+        .sum();
+    let sol_b: u64 = banks
+        .iter()
+        .map(|bank| largest_joltage(bank, 12).unwrap())
+        .sum();
+    (sol_a.to_string(), sol_b.to_string())
 }
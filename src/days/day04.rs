@@ -16,7 +16,22 @@
 
 use std::collections::VecDeque;
 
+#[cfg(feature = "animate")]
+use crate::animate::hue;
+#[cfg(feature = "animate")]
+use crate::animate::paint;
+#[cfg(feature = "animate")]
+use crate::animate::Animation;
+use crate::common::nonempty_lines;
+use crate::common::ParseError;
 use crate::common::Solution;
+use crate::render::Drawing;
+use crate::render::Shape;
+use crate::render::BLACK;
+use crate::render::RED;
+use crate::render::WHITE;
+
+type Pos = (usize, usize);
 
 fn neighbors<'a, 'b>(
     rolls: &'a [Vec<bool>],
@@ -36,58 +51,228 @@ fn neighbors<'a, 'b>(
     .filter(|(rr, cc)| rolls[*rr][*cc])
 }
 
-fn solve_a(
-    grid: &[Vec<bool>],
-    (rolls_a, rolls_b): (&[(usize, usize)], &[(usize, usize)]),
-) -> usize {
-    rolls_a
+/// The grid of rolls with an empty border of width 1, and the positions of the rolls.
+pub struct Grid {
+    grid: Vec<Vec<bool>>,
+    rolls: VecDeque<Pos>,
+}
+
+pub fn solve_a(Grid { grid, rolls }: &Grid) -> usize {
+    rolls
         .iter()
-        .chain(rolls_b.iter())
         .filter(|rc| neighbors(grid, rc).count() < 4)
         .count()
 }
 
-fn solve_b(mut rolls: Vec<Vec<bool>>, mut remove_queue: VecDeque<(usize, usize)>) -> usize {
+/// The rolls left when no more can be removed, and the number of rolls removed.
+fn remove_accessible(grid: &[Vec<bool>], rolls: &VecDeque<Pos>) -> (Vec<Vec<bool>>, usize) {
+    let mut rolls_left = grid.to_vec();
+    let mut remove_queue = rolls.clone();
     let mut removed = 0;
     while let Some((r, c)) = remove_queue.pop_front() {
-        if rolls[r][c] && neighbors(&rolls, &(r, c)).count() < 4 {
+        if rolls_left[r][c] && neighbors(&rolls_left, &(r, c)).count() < 4 {
             removed += 1;
-            rolls[r][c] = false;
-            for rrcc in neighbors(&rolls, &(r, c)) {
+            rolls_left[r][c] = false;
+            for rrcc in neighbors(&rolls_left, &(r, c)) {
                 remove_queue.push_back(rrcc);
             }
         }
     }
-    removed
+    (rolls_left, removed)
+}
+
+pub fn solve_b(Grid { grid, rolls }: &Grid) -> usize {
+    remove_accessible(grid, rolls).1
+}
+
+/// The rolls left after part B in black, and the removed rolls in red.
+pub fn render(lines: &[String]) -> Drawing {
+    let Grid { grid, rolls } = parse(lines).unwrap_or_else(|e| panic!("{e}"));
+    let (rolls_left, _) = remove_accessible(&grid, &rolls);
+    let coordinate = |i: usize| f64::from(u32::try_from(i).expect("Grid too large to draw"));
+    let mut drawing = Drawing::new(
+        coordinate(grid[0].len() - 2),
+        coordinate(grid.len() - 2),
+        WHITE,
+    );
+    for (r, c) in rolls {
+        drawing.push(Shape::Rect {
+            x: coordinate(c - 1),
+            y: coordinate(r - 1),
+            width: 1.0,
+            height: 1.0,
+            fill: if rolls_left[r][c] { BLACK } else { RED },
+        });
+    }
+    drawing
+}
+
+/// Replay the removal in part B as an animation, one frame per wave: the initial rolls are in the
+/// first wave, and rolls revisited after a neighbour is removed are in the wave after that
+/// neighbour's.
+#[cfg(feature = "animate")]
+pub fn animate(Grid { grid, rolls }: &Grid) {
+    let mut rolls_left = grid.to_vec();
+    let mut removed_in: Vec<Vec<Option<usize>>> = vec![vec![None; grid[0].len()]; grid.len()];
+    let mut queue: VecDeque<(Pos, usize)> = rolls.iter().map(|rc| (*rc, 0)).collect();
+    let mut animation = Animation::new();
+    let mut current_wave = 0;
+    let mut removed = 0;
+    while let Some(((r, c), wave)) = queue.pop_front() {
+        if wave > current_wave {
+            animation.frame(&render_removal(
+                &rolls_left,
+                &removed_in,
+                &format!("Wave {}: {removed} rolls removed", current_wave + 1),
+            ));
+            current_wave = wave;
+        }
+        if rolls_left[r][c] && neighbors(&rolls_left, &(r, c)).count() < 4 {
+            removed += 1;
+            rolls_left[r][c] = false;
+            removed_in[r][c] = Some(wave);
+            for rrcc in neighbors(&rolls_left, &(r, c)) {
+                queue.push_back((rrcc, wave + 1));
+            }
+        }
+    }
+    let remaining = rolls_left.iter().flatten().filter(|roll| **roll).count();
+    animation.frame(&render_removal(
+        &rolls_left,
+        &removed_in,
+        &format!(
+            "Stable after {} waves: {removed} rolls removed, {remaining} remain",
+            current_wave + 1
+        ),
+    ));
+}
+
+/// Remaining rolls in white and removed rolls coloured by their wave, without the border.
+#[cfg(feature = "animate")]
+fn render_removal(
+    rolls_left: &[Vec<bool>],
+    removed_in: &[Vec<Option<usize>>],
+    caption: &str,
+) -> String {
+    let rows = rolls_left.len() - 1;
+    let cols = rolls_left[0].len() - 1;
+    let mut frame = format!("{caption}\n");
+    for r in 1..rows {
+        for c in 1..cols {
+            match (rolls_left[r][c], removed_in[r][c]) {
+                (true, _) => frame.push('@'),
+                (false, Some(wave)) => frame.push_str(&paint(hue(wave), 'x')),
+                (false, None) => frame.push('.'),
+            }
+        }
+        frame.push('\n');
+    }
+    frame
+}
+
+pub fn parse(lines: &[String]) -> Result<Grid, ParseError> {
+    let rows: Vec<Vec<bool>> = nonempty_lines(lines)
+        .map(|(l, line)| {
+            line.chars()
+                .map(|ch| match ch {
+                    '@' => Ok(true),
+                    '.' => Ok(false),
+                    _ => Err(ParseError::new(l, format!("Invalid cell: {ch:?}"))),
+                })
+                .collect()
+        })
+        .collect::<Result<_, _>>()?;
+    if rows.iter().any(|row| row.len() != rows[0].len()) {
+        return Err(ParseError::input("Expected all rows to be the same length"));
+    }
+
+    let width = rows.first().map(Vec::len).unwrap_or(0);
+    let (grid, rolls): (Vec<Vec<bool>>, VecDeque<Pos>) = rows.iter().enumerate().fold(
+        (
+            vec![vec![false; width + 2]; rows.len() + 2],
+            VecDeque::with_capacity(rows.len() * width / 2),
+        ),
+        |(grid, rolls), (r, row)| {
+            row.iter()
+                .enumerate()
+                .fold((grid, rolls), move |(mut grid, mut rolls), (c, roll)| {
+                    if *roll {
+                        grid[r + 1][c + 1] = true;
+                        rolls.push_back((r + 1, c + 1));
+                    }
+                    (grid, rolls)
+                })
+        },
+    );
+    Ok(Grid { grid, rolls })
 }
 
 pub fn solve(lines: &[String]) -> Solution {
-    let (grid, rolls): (Vec<Vec<bool>>, VecDeque<(usize, usize)>) = lines
+    let grid = parse(lines).unwrap_or_else(|e| panic!("{e}"));
+    (solve_a(&grid).to_string(), solve_b(&grid).to_string())
+}
+
+/// The grid with the rolls accessible in part A marked `x`, and the grid with the rolls removed in
+/// part B marked `x`.
+pub fn explain(lines: &[String]) -> Vec<String> {
+    let Grid { grid, rolls } = parse(lines).unwrap_or_else(|e| panic!("{e}"));
+    let (rolls_left, _) = remove_accessible(&grid, &rolls);
+    let draw = |marked: &dyn Fn(Pos) -> bool| -> Vec<String> {
+        (1..grid.len() - 1)
+            .map(|r| {
+                (1..grid[r].len() - 1)
+                    .map(|c| match (grid[r][c], marked((r, c))) {
+                        (true, true) => 'x',
+                        (true, false) => '@',
+                        (false, _) => '.',
+                    })
+                    .collect::<String>()
+            })
+            .collect()
+    };
+    std::iter::once("Accessible rolls:".to_string())
+        .chain(draw(&|rc| neighbors(&grid, &rc).count() < 4))
+        .chain(std::iter::once("Removed rolls:".to_string()))
+        .chain(draw(&|(r, c)| !rolls_left[r][c]))
+        .collect()
+}
+
+fn accessible(grid: &[Vec<bool>], r: usize, c: usize) -> bool {
+    let neighbors = (r.saturating_sub(1)..=r + 1)
+        .flat_map(|rr| (c.saturating_sub(1)..=c + 1).map(move |cc| (rr, cc)))
+        .filter(|(rr, cc)| (*rr, *cc) != (r, c))
+        .filter(|(rr, cc)| grid.get(*rr).and_then(|row| row.get(*cc)) == Some(&true))
+        .count();
+    neighbors < 4
+}
+
+/// Reference implementation: scan the whole grid, and for part B remove every accessible roll
+/// in rounds until none are left.
+pub fn solve_reference(lines: &[String]) -> Solution {
+    let mut grid: Vec<Vec<bool>> = lines
         .iter()
         .map(|line| line.trim())
         .filter(|line| !line.is_empty())
-        .enumerate()
-        .map(|(r, el)| (r + 1, el))
-        .fold(
-            (
-                vec![vec![false; lines[0].len() + 2]; lines.len() + 2],
-                VecDeque::with_capacity(lines.len() * lines[0].len() / 2),
-            ),
-            |(grid, rolls), (r, line)| {
-                line.chars().enumerate().map(|(c, el)| (c + 1, el)).fold(
-                    (grid, rolls),
-                    move |(mut grid, mut rolls), (c, ch)| {
-                        if ch == '@' {
-                            grid[r][c] = true;
-                            rolls.push_back((r, c));
-                        }
-                        (grid, rolls)
-                    },
-                )
-            },
-        );
-    (
-        solve_a(&grid, rolls.as_slices()).to_string(),
-        solve_b(grid, rolls).to_string(),
-    )
+        .map(|line| line.chars().map(|ch| ch == '@').collect())
+        .collect();
+    let accessible_rolls = |grid: &[Vec<bool>]| -> Vec<(usize, usize)> {
+        (0..grid.len())
+            .flat_map(|r| (0..grid[r].len()).map(move |c| (r, c)))
+            .filter(|(r, c)| grid[*r][*c] && accessible(grid, *r, *c))
+            .collect()
+    };
+
+    let sol_a = accessible_rolls(&grid).len();
+    let mut sol_b = 0;
+    loop {
+        let removable = accessible_rolls(&grid);
+        if removable.is_empty() {
+            break;
+        }
+        sol_b += removable.len();
+        for (r, c) in removable {
+            grid[r][c] = false;
+        }
+    }
+    (sol_a.to_string(), sol_b.to_string())
 }
@@ -16,6 +16,9 @@
 
 use std::ops::RangeInclusive;
 
+use crate::common::parse_number;
+use crate::common::trace;
+use crate::common::ParseError;
 use crate::common::Solution;
 
 fn merge<T>(a: &RangeInclusive<T>, b: &RangeInclusive<T>) -> Option<RangeInclusive<T>>
@@ -50,33 +53,114 @@ where
     ranges
 }
 
-fn solve_a(fresh: &[RangeInclusive<usize>], ids: &[usize]) -> usize {
-    ids.iter()
-        .filter(|id| {
-            let max = fresh.partition_point(|range| range.start() < id);
-            fresh[..max].iter().rev().any(|range| range.contains(id))
-        })
-        .count()
+/// The fresh ID ranges, sorted and merged so that they do not overlap, and the available IDs.
+pub struct Inventory {
+    fresh: Vec<RangeInclusive<usize>>,
+    ids: Vec<usize>,
 }
 
-fn solve_b(fresh: Vec<RangeInclusive<usize>>) -> usize {
-    fresh.into_iter().map(|range| range.count()).sum()
+fn is_fresh(fresh: &[RangeInclusive<usize>], id: &usize) -> bool {
+    let max = fresh.partition_point(|range| range.start() <= id);
+    fresh[..max].iter().rev().any(|range| range.contains(id))
 }
 
-pub fn solve(lines: &[String]) -> Solution {
-    let (fresh, ids): (Vec<RangeInclusive<usize>>, Vec<usize>) = {
-        let mut fresh = Vec::new();
-        let mut it = lines.iter();
-        while let Some((l, r)) = it.next().and_then(|line| line.trim().split_once('-')) {
-            fresh.push(l.parse().unwrap()..=r.parse().unwrap());
+pub fn solve_a(Inventory { fresh, ids }: &Inventory) -> usize {
+    ids.iter().filter(|id| is_fresh(fresh, id)).count()
+}
+
+pub fn solve_b(Inventory { fresh, .. }: &Inventory) -> usize {
+    trace!("{} fresh ranges after merging: {fresh:?}", fresh.len());
+    fresh.iter().map(|range| range.clone().count()).sum()
+}
+
+/// The fresh ID ranges end at the first blank line, and the available IDs follow them.
+pub fn parse(lines: &[String]) -> Result<Inventory, ParseError> {
+    let mut fresh = Vec::new();
+    let mut ids = Vec::new();
+    let mut in_ranges = true;
+    for (i, line) in lines.iter().map(|line| line.trim()).enumerate() {
+        let l = i + 1;
+        if line.is_empty() {
+            in_ranges = fresh.is_empty();
+        } else if in_ranges {
+            let (lo, hi) = line
+                .split_once('-')
+                .ok_or_else(|| ParseError::new(l, format!("Invalid range: {line:?}")))?;
+            let (lo, hi) = (parse_number(l, lo)?, parse_number(l, hi)?);
+            if lo > hi {
+                return Err(ParseError::new(l, format!("Empty range: {line:?}")));
+            }
+            fresh.push(lo..=hi);
+        } else {
+            ids.push(parse_number(l, line)?);
         }
-        fresh.sort_by_key(|range| *range.start());
-        fresh = merge_all(fresh);
-        let ids = it.flat_map(|line| line.trim().parse().ok()).collect();
-        (fresh, ids)
-    };
+    }
+    fresh.sort_by_key(|range| *range.start());
+    Ok(Inventory {
+        fresh: merge_all(fresh),
+        ids,
+    })
+}
+
+pub fn solve(lines: &[String]) -> Solution {
+    let inventory = parse(lines).unwrap_or_else(|e| panic!("{e}"));
     (
-        solve_a(&fresh, &ids).to_string(),
-        solve_b(fresh).to_string(),
+        solve_a(&inventory).to_string(),
+        solve_b(&inventory).to_string(),
     )
 }
+
+/// The fresh IDs of part A, and the merged ranges counted in part B.
+pub fn explain(lines: &[String]) -> Vec<String> {
+    let Inventory { fresh, ids } = parse(lines).unwrap_or_else(|e| panic!("{e}"));
+    let fresh_ids: Vec<&usize> = ids.iter().filter(|id| is_fresh(&fresh, id)).collect();
+    std::iter::once(format!("Fresh IDs: {fresh_ids:?}"))
+        .chain(std::iter::once("Merged fresh ranges:".to_string()))
+        .chain(fresh.iter().map(|range| {
+            format!(
+                "{}-{}: {} IDs",
+                range.start(),
+                range.end(),
+                range.clone().count()
+            )
+        }))
+        .collect()
+}
+
+/// Reference implementation: check every ID against every range, and count fresh IDs between
+/// consecutive range boundaries.
+pub fn solve_reference(lines: &[String]) -> Solution {
+    let mut it = lines.iter().map(|line| line.trim());
+    let fresh: Vec<(usize, usize)> = it
+        .by_ref()
+        .take_while(|line| !line.is_empty())
+        .map(|line| {
+            let (l, r) = line.split_once('-').unwrap();
+            (l.parse().unwrap(), r.parse().unwrap())
+        })
+        .collect();
+    let ids: Vec<usize> = it
+        .filter(|line| !line.is_empty())
+        .map(|line| line.parse().unwrap())
+        .collect();
+
+    let sol_a = ids
+        .iter()
+        .filter(|id| fresh.iter().any(|(l, r)| l <= *id && *id <= r))
+        .count();
+
+    let mut boundaries: Vec<usize> = fresh.iter().flat_map(|(l, r)| [*l, r + 1]).collect();
+    boundaries.sort();
+    boundaries.dedup();
+    let sol_b: usize = boundaries
+        .windows(2)
+        .filter(|window| {
+            fresh
+                .iter()
+                .any(|(l, r)| *l <= window[0] && window[0] <= *r)
+        })
+        .map(|window| window[1] - window[0])
+        .sum();
+
+    (sol_a.to_string(), sol_b.to_string())
+}
@@ -14,138 +14,258 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use std::ops::Range;
+
+use crate::common::parse_number;
+use crate::common::ParseError;
 use crate::common::Solution;
 
-struct Problem {
+/// More rows of operands could overflow a `u64` when read by columns.
+const MAX_OPERAND_ROWS: usize = 19;
+
+pub struct Problem {
     operands: Vec<u64>,
     mul: bool,
 }
 
-#[derive(Debug)]
-struct ProblemBParsing {
-    operands: Vec<Vec<(usize, u64)>>,
-    mul: bool,
+/// The problems read by rows, and the operand rows as written for reading them by columns.
+pub struct Worksheet {
+    by_rows: Vec<Problem>,
+    operand_rows: Vec<String>,
 }
 
-fn solve_a(problems: &[Problem]) -> u64 {
-    problems
-        .iter()
-        .map(|Problem { operands, mul }| {
-            if *mul {
-                operands.iter().copied().product::<u64>()
-            } else {
-                operands.iter().copied().sum()
-            }
+impl Problem {
+    /// The value, or `None` if it does not fit in a `u64`.
+    fn checked_value(&self) -> Option<u64> {
+        if self.mul {
+            self.operands
+                .iter()
+                .try_fold(1, |p: u64, n| p.checked_mul(*n))
+        } else {
+            self.operands
+                .iter()
+                .try_fold(0, |s: u64, n| s.checked_add(*n))
+        }
+    }
+
+    fn value(&self) -> u64 {
+        if self.mul {
+            self.operands.iter().copied().product()
+        } else {
+            self.operands.iter().copied().sum()
+        }
+    }
+}
+
+impl std::fmt::Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let operands: Vec<String> = self.operands.iter().map(u64::to_string).collect();
+        let op = if self.mul { " * " } else { " + " };
+        write!(f, "{} = {}", operands.join(op), self.value())
+    }
+}
+
+fn evaluate(problems: &[Problem]) -> u64 {
+    problems.iter().map(Problem::value).sum()
+}
+
+/// Read each problem's operands by columns: one number per column, most significant digit at
+/// the top.
+fn read_columns(operand_rows: &[String], problems: &[Problem]) -> Vec<Problem> {
+    let column = |c: usize| -> Option<u64> {
+        operand_rows
+            .iter()
+            .flat_map(|row| row.as_bytes().get(c))
+            .filter(|b| b.is_ascii_digit())
+            .fold(None, |n, d| Some(n.unwrap_or(0) * 10 + u64::from(d - b'0')))
+    };
+
+    let width = operand_rows.iter().map(String::len).max().unwrap_or(0);
+    let mut columns = Vec::with_capacity(problems.len());
+    let mut numbers = Vec::new();
+    for c in 0..=width {
+        if let Some(n) = column(c) {
+            numbers.push(n);
+        } else if !numbers.is_empty() {
+            columns.push(std::mem::take(&mut numbers));
+        }
+    }
+    columns
+        .into_iter()
+        .zip(problems)
+        .map(|(operands, problem)| Problem {
+            operands,
+            mul: problem.mul,
         })
-        .sum()
+        .collect()
+}
+
+pub fn solve_a(worksheet: &Worksheet) -> u64 {
+    evaluate(&worksheet.by_rows)
 }
 
-fn solve_b(lines: &[String]) -> u64 {
-    let problems: Vec<ProblemBParsing> = lines
+pub fn solve_b(worksheet: &Worksheet) -> u64 {
+    evaluate(&read_columns(&worksheet.operand_rows, &worksheet.by_rows))
+}
+
+pub fn parse(lines: &[String]) -> Result<Worksheet, ParseError> {
+    let rows: Vec<(usize, &str)> = lines
         .iter()
-        .filter(|line| !line.is_empty())
         .enumerate()
-        .fold(Vec::new(), |problems: Vec<ProblemBParsing>, (r, line)| {
-            let (problems, _, _) = line.chars().enumerate().fold(
-                (problems, 0, true),
-                |(mut problems, problem_i, last_was_whitespace): (
-                    Vec<ProblemBParsing>,
-                    usize,
-                    bool,
-                ),
-                 (c, ch)| {
-                    if ch == ' ' {
-                        if last_was_whitespace {
-                            (problems, problem_i, true)
-                        } else {
-                            (problems, problem_i + 1, true)
-                        }
-                    } else {
-                        if problems.len() <= problem_i {
-                            problems.resize_with(problem_i + 1, || ProblemBParsing {
-                                operands: Vec::with_capacity(4),
-                                mul: false,
-                            });
-                        }
-                        match ch {
-                            '+' | '*' => {
-                                problems[problem_i].mul = ch == '*';
-                                (problems, problem_i, false)
-                            }
-                            digit => {
-                                problems[problem_i].operands.resize_with(r + 1, Vec::new);
-                                problems[problem_i].operands[r]
-                                    .push((c, digit.to_digit(10).unwrap().into()));
-                                (problems, problem_i, false)
-                            }
-                        }
-                    }
-                },
-            );
-            problems
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| (i + 1, line.as_str()))
+        .collect();
+    let Some(((ol, operators), operands)) = rows.split_last() else {
+        return Ok(Worksheet {
+            by_rows: Vec::new(),
+            operand_rows: Vec::new(),
         });
-    solve_a(
-        &problems
-            .into_iter()
-            .map(|problem| {
-                let min_c = problem
-                    .operands
-                    .iter()
-                    .flat_map(|op| op.iter())
-                    .map(|(c, _)| c)
-                    .min()
-                    .copied()
-                    .unwrap();
-                let max_c = problem
-                    .operands
-                    .iter()
-                    .flat_map(|op| op.iter())
-                    .map(|(c, _)| c)
-                    .max()
-                    .copied()
-                    .unwrap();
-                Problem {
-                    operands: problem.operands.into_iter().fold(
-                        vec![0; max_c + 1 - min_c],
-                        |operands, digits| {
-                            digits
-                                .into_iter()
-                                .fold(operands, |mut operands, (c, digit)| {
-                                    let cc = c - min_c;
-                                    operands[cc] = operands[cc] * 10 + digit;
-                                    operands
-                                })
-                        },
-                    ),
-                    mul: problem.mul,
-                }
+    };
+    if operands.len() > MAX_OPERAND_ROWS {
+        return Err(ParseError::input(format!(
+            "Expected at most {MAX_OPERAND_ROWS} rows of operands"
+        )));
+    }
+
+    if let Some(ch) = operators.chars().find(|ch| !"+* ".contains(*ch)) {
+        return Err(ParseError::new(*ol, format!("Invalid operator: {ch:?}")));
+    }
+    let mut by_rows: Vec<Problem> = operators
+        .split_ascii_whitespace()
+        .map(|op| match op {
+            "+" => Ok(false),
+            "*" => Ok(true),
+            _ => Err(ParseError::new(*ol, format!("Invalid operator: {op:?}"))),
+        })
+        .map(|mul| {
+            mul.map(|mul| Problem {
+                operands: Vec::with_capacity(operands.len()),
+                mul,
             })
-            .collect::<Vec<_>>(),
-    )
+        })
+        .collect::<Result<_, _>>()?;
+
+    for (l, line) in operands {
+        if let Some(ch) = line.chars().find(|ch| !ch.is_ascii_digit() && *ch != ' ') {
+            return Err(ParseError::new(*l, format!("Invalid character: {ch:?}")));
+        }
+        let numbers: Vec<u64> = line
+            .split_ascii_whitespace()
+            .map(|n| parse_number(*l, n))
+            .collect::<Result<_, _>>()?;
+        if numbers.len() != by_rows.len() {
+            return Err(ParseError::new(
+                *l,
+                format!(
+                    "Expected {} operands, found {}",
+                    by_rows.len(),
+                    numbers.len()
+                ),
+            ));
+        }
+        for (problem, n) in by_rows.iter_mut().zip(numbers) {
+            problem.operands.push(n);
+        }
+    }
+
+    let operand_rows: Vec<String> = operands.iter().map(|(_, line)| line.to_string()).collect();
+    for problems in [&by_rows, &read_columns(&operand_rows, &by_rows)] {
+        problems
+            .iter()
+            .try_fold(0, |sum: u64, problem| {
+                sum.checked_add(problem.checked_value()?)
+            })
+            .ok_or_else(|| ParseError::input("The grand total does not fit in a u64"))?;
+    }
+
+    Ok(Worksheet {
+        by_rows,
+        operand_rows,
+    })
 }
 
 pub fn solve(lines: &[String]) -> Solution {
-    let problems: Vec<Problem> = lines
+    let worksheet = parse(lines).unwrap_or_else(|e| panic!("{e}"));
+    (
+        solve_a(&worksheet).to_string(),
+        solve_b(&worksheet).to_string(),
+    )
+}
+
+/// Each problem as read by rows in part A and by columns in part B.
+pub fn explain(lines: &[String]) -> Vec<String> {
+    let worksheet = parse(lines).unwrap_or_else(|e| panic!("{e}"));
+    let by_columns = read_columns(&worksheet.operand_rows, &worksheet.by_rows);
+    worksheet
+        .by_rows
+        .iter()
+        .zip(&by_columns)
+        .enumerate()
+        .map(|(i, (by_row, by_column))| format!("Problem {}: A {by_row}, B {by_column}", i + 1))
+        .collect()
+}
+
+/// Reference implementation: split the worksheet into blocks of columns separated by blank
+/// columns, then read each block by rows for part A and by columns for part B.
+pub fn solve_reference(lines: &[String]) -> Solution {
+    let rows: Vec<Vec<char>> = lines
         .iter()
-        .map(|line| line.trim())
-        .filter(|line| !line.is_empty())
-        .fold(Vec::new(), |problems, line| {
-            line.split_ascii_whitespace()
-                .enumerate()
-                .fold(problems, |mut problems, (i, op)| {
-                    if problems.len() <= i {
-                        problems.resize_with(i + 1, || Problem {
-                            operands: Vec::with_capacity(4),
-                            mul: false,
-                        });
-                    }
-                    if op == "+" || op == "*" {
-                        problems[i].mul = op == "*";
-                    } else {
-                        problems[i].operands.push(op.parse().unwrap());
-                    }
-                    problems
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.chars().collect())
+        .collect();
+    let width = rows.iter().map(|row| row.len()).max().unwrap();
+    let cell = |r: usize, c: usize| rows[r].get(c).copied().unwrap_or(' ');
+    let operator_row = rows.len() - 1;
+    let blank_column = |c: usize| (0..rows.len()).all(|r| cell(r, c) == ' ');
+
+    let mut blocks: Vec<Range<usize>> = Vec::new();
+    let mut start = 0;
+    for c in 0..=width {
+        if c == width || blank_column(c) {
+            if c > start {
+                blocks.push(start..c);
+            }
+            start = c + 1;
+        }
+    }
+
+    let evaluate = |block: &Range<usize>, numbers: Vec<u64>| -> u64 {
+        let op: String = block.clone().map(|c| cell(operator_row, c)).collect();
+        match op.trim() {
+            "+" => numbers.into_iter().sum(),
+            "*" => numbers.into_iter().product(),
+            op => panic!("Invalid operator: {op}"),
+        }
+    };
+
+    let sol_a: u64 = blocks
+        .iter()
+        .map(|block| {
+            let numbers = (0..operator_row)
+                .map(|r| {
+                    let s: String = block.clone().map(|c| cell(r, c)).collect();
+                    s.trim().parse().unwrap()
                 })
-        });
-    (solve_a(&problems).to_string(), solve_b(lines).to_string())
+                .collect();
+            evaluate(block, numbers)
+        })
+        .sum();
+    let sol_b: u64 = blocks
+        .iter()
+        .map(|block| {
+            let numbers = block
+                .clone()
+                .map(|c| {
+                    let s: String = (0..operator_row)
+                        .map(|r| cell(r, c))
+                        .filter(|ch| *ch != ' ')
+                        .collect();
+                    s.parse().unwrap()
+                })
+                .collect();
+            evaluate(block, numbers)
+        })
+        .sum();
+
+    (sol_a.to_string(), sol_b.to_string())
 }
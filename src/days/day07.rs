@@ -14,33 +14,244 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+
+#[cfg(feature = "animate")]
+use crate::animate::heat;
+#[cfg(feature = "animate")]
+use crate::animate::paint;
+#[cfg(feature = "animate")]
+use crate::animate::Animation;
+use crate::common::nonempty_lines;
+use crate::common::ParseError;
 use crate::common::Solution;
 
+/// The column of the source, and the splitters on each row below it. Splitters are never at the
+/// edges, so every split beam stays inside the manifold.
+pub fn parse(lines: &[String]) -> Result<(usize, Vec<Vec<bool>>), ParseError> {
+    let mut it = nonempty_lines(lines);
+    let (l, first) = it
+        .next()
+        .ok_or_else(|| ParseError::input("Expected a source row"))?;
+    if first.chars().filter(|ch| *ch == 'S').count() != 1
+        || first.contains(|ch| ch != 'S' && ch != '.')
+    {
+        return Err(ParseError::new(l, "Expected a row with a single source"));
+    }
+    let width = first.len();
+    let start = first.find('S').unwrap();
+
+    let rows = it
+        .map(|(l, line)| {
+            let row: Vec<bool> = line
+                .chars()
+                .map(|ch| match ch {
+                    '^' => Ok(true),
+                    '.' => Ok(false),
+                    _ => Err(ParseError::new(l, format!("Invalid cell: {ch:?}"))),
+                })
+                .collect::<Result<_, _>>()?;
+            if row.len() != width {
+                Err(ParseError::new(
+                    l,
+                    format!("Expected a row of width {width}"),
+                ))
+            } else if row[0] || row[width - 1] {
+                Err(ParseError::new(l, "Expected no splitters at the edges"))
+            } else {
+                Ok(row)
+            }
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((start, rows))
+}
+
+/// The number of timelines ending in each column, and the number of times the beam is split.
+fn simulate(manifold: &(usize, Vec<Vec<bool>>)) -> (Vec<u64>, usize) {
+    let mut splits = 0;
+    let paths = trace_rows(manifold, |_, hits| splits += hits.len());
+    (paths, splits)
+}
+
+/// Pass the beam from the source through each row of splitters in turn, calling `after_row` with
+/// the timelines in each column and the columns of the splitters hit on that row. Returns the
+/// timelines ending in each column.
+fn trace_rows(
+    (start, rows): &(usize, Vec<Vec<bool>>),
+    mut after_row: impl FnMut(&[u64], &[usize]),
+) -> Vec<u64> {
+    let mut paths = vec![0; rows.first().map(Vec::len).unwrap_or(start + 1)];
+    paths[*start] = 1;
+    let mut hits = Vec::new();
+    for row in rows {
+        hits.clear();
+        split_row(&mut paths, row, |i| hits.push(i));
+        after_row(&paths, &hits);
+    }
+    paths
+}
+
+/// Pass the timelines in each column through a row of splitters, calling `hit` with the column of
+/// each splitter that a beam hits.
+fn split_row(paths: &mut [u64], row: &[bool], mut hit: impl FnMut(usize)) {
+    for (i, splitter) in row.iter().enumerate() {
+        if *splitter && paths[i] > 0 {
+            paths[i - 1] += paths[i];
+            paths[i + 1] += paths[i];
+            paths[i] = 0;
+            hit(i);
+        }
+    }
+}
+
+/// Replay the simulation as an animation, one frame per row. Beams are coloured by the number of
+/// timelines in their column on a logarithmic heat scale, and splitters are highlighted when a beam
+/// hits them.
+#[cfg(feature = "animate")]
+pub fn animate(manifold: &(usize, Vec<Vec<bool>>)) {
+    let (start, rows) = manifold;
+    let mut history: Vec<(Vec<u64>, Vec<bool>)> = Vec::with_capacity(rows.len());
+    let mut animation = Animation::new();
+    let mut splits = 0;
+    trace_rows(manifold, |paths, hits| {
+        let mut hit = vec![false; paths.len()];
+        for &i in hits {
+            hit[i] = true;
+        }
+        splits += hits.len();
+        history.push((paths.to_vec(), hit));
+        animation.frame(&render_beams(*start, rows, &history, splits));
+    });
+}
+
+#[cfg(feature = "animate")]
+fn render_beams(
+    start: usize,
+    rows: &[Vec<bool>],
+    history: &[(Vec<u64>, Vec<bool>)],
+    splits: usize,
+) -> String {
+    let max = history
+        .iter()
+        .flat_map(|(paths, _)| paths)
+        .copied()
+        .max()
+        .unwrap_or(1);
+    let log_max = (max as f64).ln().max(1.0);
+    let timelines: u64 = history
+        .last()
+        .map(|(paths, _)| paths.iter().sum())
+        .unwrap_or(1);
+    let mut frame = format!(
+        "Row {}: {splits} splits, {timelines} timelines, at most {max} in one column\n",
+        history.len()
+    );
+    frame.extend(
+        (0..rows.first().map(Vec::len).unwrap_or(start + 1)).map(|c| {
+            if c == start {
+                'S'
+            } else {
+                '.'
+            }
+        }),
+    );
+    frame.push('\n');
+    for (row, (paths, hits)) in rows.iter().zip(history) {
+        for (c, splitter) in row.iter().enumerate() {
+            if hits[c] {
+                frame.push_str(&paint(196, '^'));
+            } else if *splitter {
+                frame.push_str(&paint(240, '^'));
+            } else if paths[c] > 0 {
+                frame.push_str(&paint(heat((paths[c] as f64).ln() / log_max), '|'));
+            } else {
+                frame.push('.');
+            }
+        }
+        frame.push('\n');
+    }
+    frame
+}
+
+pub fn solve_a(manifold: &(usize, Vec<Vec<bool>>)) -> usize {
+    simulate(manifold).1
+}
+
+pub fn solve_b(manifold: &(usize, Vec<Vec<bool>>)) -> u64 {
+    simulate(manifold).0.into_iter().sum()
+}
+
 pub fn solve(lines: &[String]) -> Solution {
-    let mut it = lines
+    let manifold = parse(lines).unwrap_or_else(|e| panic!("{e}"));
+    let (paths, sol_a) = simulate(&manifold);
+    let sol_b: u64 = paths.into_iter().sum();
+    (sol_a.to_string(), sol_b.to_string())
+}
+
+/// The splitters hit on each row in part A, and the timelines ending in each column in part B.
+pub fn explain(lines: &[String]) -> Vec<String> {
+    let manifold = parse(lines).unwrap_or_else(|e| panic!("{e}"));
+    let mut explanation = Vec::new();
+    let mut row = 1;
+    let paths = trace_rows(&manifold, |_, hits| {
+        row += 1;
+        if !hits.is_empty() {
+            explanation.push(format!("Row {row}: splitters hit in columns {hits:?}"));
+        }
+    });
+    explanation.push(format!("Timelines ending in each column: {paths:?}"));
+    explanation
+}
+
+fn count_timelines(
+    grid: &[Vec<char>],
+    r: usize,
+    c: usize,
+    memo: &mut HashMap<(usize, usize), u64>,
+) -> u64 {
+    if r + 1 >= grid.len() {
+        1
+    } else if let Some(count) = memo.get(&(r, c)) {
+        *count
+    } else {
+        let count = if grid[r + 1][c] == '^' {
+            count_timelines(grid, r + 1, c - 1, memo) + count_timelines(grid, r + 1, c + 1, memo)
+        } else {
+            count_timelines(grid, r + 1, c, memo)
+        };
+        memo.insert((r, c), count);
+        count
+    }
+}
+
+/// Reference implementation: trace the set of beam columns row by row for part A, and follow
+/// each timeline recursively from the source for part B.
+pub fn solve_reference(lines: &[String]) -> Solution {
+    let grid: Vec<Vec<char>> = lines
         .iter()
         .map(|line| line.trim())
-        .filter(|line| !line.is_empty());
-    let paths = it
-        .next()
-        .unwrap()
-        .chars()
-        .map(|ch| if ch == 'S' { 1 } else { 0 })
+        .filter(|line| !line.is_empty())
+        .map(|line| line.chars().collect())
         .collect();
-    let (paths, sol_a): (Vec<u64>, usize) = it.fold((paths, 0), |(paths, splits), line| {
-        line.chars()
-            .enumerate()
-            .fold((paths, splits), |(mut paths, splits), (i, ch)| {
-                if ch == '^' && paths[i] > 0 {
-                    paths[i - 1] += paths[i];
-                    paths[i + 1] += paths[i];
-                    paths[i] = 0;
-                    (paths, splits + 1)
+    let start = grid[0].iter().position(|ch| *ch == 'S').unwrap();
+
+    let mut beams = BTreeSet::from([start]);
+    let mut splits = 0;
+    for row in &grid[1..] {
+        beams = beams
+            .into_iter()
+            .flat_map(|c| {
+                if row[c] == '^' {
+                    splits += 1;
+                    vec![c - 1, c + 1]
                 } else {
-                    (paths, splits)
+                    vec![c]
                 }
             })
-    });
-    let sol_b: u64 = paths.into_iter().sum();
-    (sol_a.to_string(), sol_b.to_string())
+            .collect();
+    }
+
+    let timelines = count_timelines(&grid, 0, start, &mut HashMap::new());
+    (splits.to_string(), timelines.to_string())
 }
@@ -15,12 +15,32 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 use std::collections::HashMap;
-use std::collections::HashSet;
 
+use crate::common::nonempty_lines;
+use crate::common::parse_number;
+use crate::common::ParseError;
 use crate::common::Solution;
+use crate::render::palette;
+use crate::render::Drawing;
+use crate::render::Shape;
+use crate::render::GRAY;
+use crate::render::WHITE;
+use crate::util::dsu::Dsu;
+
+/// Bound on coordinates so that squared distances fit in an `i64`.
+const MAX_COORDINATE: i64 = 1_000_000;
+
+/// Number of closest pairs connected in part A.
+const CONNECTIONS_A: usize = 1000;
 
 #[derive(Eq, PartialEq)]
-struct Point(i64, i64, i64);
+pub struct Point(i64, i64, i64);
+impl std::fmt::Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self(x, y, z) = self;
+        write!(f, "{x},{y},{z}")
+    }
+}
 impl Point {
     fn dist2(&self, other: &Self) -> i64 {
         let Self(x1, y1, z1) = self;
@@ -29,110 +49,273 @@ impl Point {
     }
 }
 
-fn connect(
-    circuits: &mut HashMap<usize, HashSet<usize>>,
-    circuit_membership: &mut HashMap<usize, usize>,
-    next_circuit_id: &mut usize,
-    ip: usize,
-    iq: usize,
-) {
-    match (
-        circuit_membership.get(&ip).copied(),
-        circuit_membership.get(&iq).copied(),
-    ) {
-        (None, None) => {
-            circuits.insert(*next_circuit_id, [ip, iq].into_iter().collect());
-            circuit_membership.insert(ip, *next_circuit_id);
-            circuit_membership.insert(iq, *next_circuit_id);
-            *next_circuit_id += 1;
-        }
-        (Some(circuit_id), None) => {
-            circuits.get_mut(&circuit_id).unwrap().insert(iq);
-            circuit_membership.insert(iq, circuit_id);
+/// The junction boxes, and all pairs of them closest first, which both parts connect in order.
+pub struct Playground {
+    points: Vec<Point>,
+    by_dist: Vec<(usize, usize)>,
+}
+
+/// The product of the sizes of the three largest circuits.
+fn largest_circuits_product(circuits: &mut Dsu) -> usize {
+    let mut sizes = circuits.component_sizes();
+    sizes.sort();
+    sizes.into_iter().rev().take(3).product()
+}
+
+/// The circuits after connecting the closest `n` pairs of the `num_points` junction boxes.
+fn circuits_after(num_points: usize, by_dist: &[(usize, usize)], n: usize) -> Dsu {
+    let mut circuits = Dsu::new(num_points);
+    for (ip, iq) in by_dist.iter().take(n) {
+        circuits.union(*ip, *iq);
+    }
+    circuits
+}
+
+/// The circuits of more than one junction box, largest first.
+fn connected_circuits(circuits: &mut Dsu) -> Vec<Vec<usize>> {
+    let mut connected: Vec<Vec<usize>> = circuits
+        .components()
+        .into_iter()
+        .filter(|circuit| circuit.len() > 1)
+        .collect();
+    connected.sort_by_key(|circuit| std::cmp::Reverse(circuit.len()));
+    connected
+}
+
+/// The answer to part A, and the pair whose connection joins all junction boxes into one circuit.
+fn solve_ab(points: &[Point], by_dist: &[(usize, usize)]) -> (usize, (usize, usize)) {
+    let mut circuits = Dsu::new(points.len());
+    let mut sol_a = None;
+
+    for (i, (ip, iq)) in by_dist.iter().copied().enumerate() {
+        if i == CONNECTIONS_A {
+            sol_a = Some(largest_circuits_product(&mut circuits));
         }
-        (None, Some(circuit_id)) => {
-            circuits.get_mut(&circuit_id).unwrap().insert(ip);
-            circuit_membership.insert(ip, circuit_id);
+
+        circuits.union(ip, iq);
+
+        if circuits.num_components() == 1 {
+            // If this happens within the first 1000 connections, the rest don't change anything
+            return (sol_a.unwrap_or(points.len()), (ip, iq));
         }
-        (Some(circuit_id_p), Some(circuit_id_q)) => {
-            if circuit_id_p != circuit_id_q {
-                if let Some(circuit_q) = circuits.remove(&circuit_id_q) {
-                    let circuit_p = circuits.get_mut(&circuit_id_p).unwrap();
-                    for iqq in circuit_q {
-                        *circuit_membership.get_mut(&iqq).unwrap() = circuit_id_p;
-                        circuit_p.insert(iqq);
-                    }
+    }
+
+    unreachable!()
+}
+
+pub fn parse(lines: &[String]) -> Result<Playground, ParseError> {
+    let points: Vec<Point> = nonempty_lines(lines)
+        .map(|(l, line)| {
+            let coords: Vec<i64> = line
+                .split(',')
+                .map(|s| parse_number(l, s))
+                .collect::<Result<_, _>>()?;
+            if let [x, y, z] = coords[..] {
+                if coords
+                    .iter()
+                    .all(|c| (-MAX_COORDINATE..=MAX_COORDINATE).contains(c))
+                {
+                    Ok(Point(x, y, z))
+                } else {
+                    Err(ParseError::new(l, "Coordinate out of range"))
                 }
+            } else {
+                Err(ParseError::new(
+                    l,
+                    format!("Expected 3 coordinates: {line:?}"),
+                ))
             }
-        }
+        })
+        .collect::<Result<_, _>>()?;
+    if points.len() < 2 {
+        Err(ParseError::input("Expected at least 2 junction boxes"))
+    } else {
+        Ok(Playground {
+            by_dist: by_distance(&points),
+            points,
+        })
     }
 }
 
-fn solve_ab(points: &[Point], by_dist: &[(usize, usize)]) -> (usize, i64) {
-    let mut circuits: HashMap<usize, HashSet<usize>> = HashMap::new();
-    let mut circuit_membership: HashMap<usize, usize> = HashMap::new();
-    let mut next_circuit_id = 0;
-
-    for (ip, iq) in by_dist.iter().copied().take(1000) {
-        connect(
-            &mut circuits,
-            &mut circuit_membership,
-            &mut next_circuit_id,
-            ip,
-            iq,
-        );
-    }
+/// All pairs of junction boxes, closest first.
+fn by_distance(points: &[Point]) -> Vec<(usize, usize)> {
+    let mut by_dist: Vec<(usize, usize)> = (0..points.len())
+        .flat_map(|ip| (ip + 1..points.len()).map(move |iq| (ip, iq)))
+        .collect();
+    by_dist.sort_by_key(|(ip, iq)| points[*ip].dist2(&points[*iq]));
+    by_dist
+}
 
-    let mut lens: Vec<usize> = circuits.values().map(|c| c.len()).collect();
-    lens.sort();
-    let sol_a = lens.into_iter().rev().take(3).product();
-
-    for (ip, iq) in by_dist[1000..].iter().copied() {
-        connect(
-            &mut circuits,
-            &mut circuit_membership,
-            &mut next_circuit_id,
-            ip,
-            iq,
-        );
-
-        if circuits.len() == 1 && circuit_membership.len() == points.len() {
-            return (sol_a, points[ip].0 * points[iq].0);
-        }
-    }
+pub fn solve_a(Playground { points, by_dist }: &Playground) -> usize {
+    solve_ab(points, by_dist).0
+}
 
-    unreachable!()
+pub fn solve_b(Playground { points, by_dist }: &Playground) -> i64 {
+    let (_, (ip, iq)) = solve_ab(points, by_dist);
+    points[ip].0 * points[iq].0
+}
+
+/// The junction boxes and the connections of part A in an isometric projection, coloured by circuit
+/// from the largest down. Boxes not connected to any other are gray.
+pub fn render(lines: &[String]) -> Drawing {
+    let Playground { points, by_dist } = parse(lines).unwrap_or_else(|e| panic!("{e}"));
+    let connections = &by_dist[..std::cmp::min(CONNECTIONS_A, by_dist.len())];
+    let mut circuits = circuits_after(points.len(), connections, CONNECTIONS_A);
+    let rank: HashMap<usize, usize> = connected_circuits(&mut circuits)
+        .into_iter()
+        .enumerate()
+        .flat_map(|(rank, circuit)| circuit.into_iter().map(move |i| (i, rank)))
+        .collect();
+    let color = |i: usize| rank.get(&i).map(|rank| palette(*rank)).unwrap_or(GRAY);
+
+    let projected: Vec<(f64, f64)> = points
+        .iter()
+        .map(|Point(x, y, z)| {
+            // Coordinates are at most MAX_COORDINATE in magnitude, so they fit in an i32.
+            let [x, y, z] =
+                [x, y, z].map(|c| f64::from(i32::try_from(*c).expect("Coordinate out of range")));
+            ((x - z) * 0.75_f64.sqrt(), (x + z) / 2.0 - y)
+        })
+        .collect();
+    let min_u = projected
+        .iter()
+        .map(|(u, _)| *u)
+        .fold(f64::INFINITY, f64::min);
+    let min_v = projected
+        .iter()
+        .map(|(_, v)| *v)
+        .fold(f64::INFINITY, f64::min);
+    let max_u = projected
+        .iter()
+        .map(|(u, _)| *u)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let max_v = projected
+        .iter()
+        .map(|(_, v)| *v)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let size = (max_u - min_u).max(max_v - min_v).max(1.0);
+    let margin = size / 50.0;
+    let at = |i: usize| {
+        let (u, v) = projected[i];
+        (u - min_u + margin, v - min_v + margin)
+    };
+
+    let mut drawing = Drawing::new(
+        max_u - min_u + 2.0 * margin,
+        max_v - min_v + 2.0 * margin,
+        WHITE,
+    );
+    for (ip, iq) in connections {
+        drawing.push(Shape::Line {
+            from: at(*ip),
+            to: at(*iq),
+            stroke: color(*ip),
+        });
+    }
+    for i in 0..points.len() {
+        let (x, y) = at(i);
+        drawing.push(Shape::Circle {
+            x,
+            y,
+            radius: size / 400.0,
+            fill: color(i),
+        });
+    }
+    drawing
 }
 
 pub fn solve(lines: &[String]) -> Solution {
-    let points: Vec<Point> = lines
+    let Playground { points, by_dist } = parse(lines).unwrap_or_else(|e| panic!("{e}"));
+    let (sol_a, (ip, iq)) = solve_ab(&points, &by_dist);
+    (sol_a.to_string(), (points[ip].0 * points[iq].0).to_string())
+}
+
+/// The three largest circuits of part A, and the closing pair of part B.
+pub fn explain(lines: &[String]) -> Vec<String> {
+    let Playground { points, by_dist } = parse(lines).unwrap_or_else(|e| panic!("{e}"));
+    let mut circuits = circuits_after(points.len(), &by_dist, CONNECTIONS_A);
+    let (_, (ip, iq)) = solve_ab(&points, &by_dist);
+    connected_circuits(&mut circuits)
+        .into_iter()
+        .take(3)
+        .map(|members| {
+            format!(
+                "Circuit of {} junction boxes: {}",
+                members.len(),
+                members
+                    .into_iter()
+                    .map(|i| points[i].to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            )
+        })
+        .chain(std::iter::once(format!(
+            "Closing pair: {} and {}",
+            points[ip], points[iq]
+        )))
+        .collect()
+}
+
+/// Reference implementation: find connected components by depth-first search over the graph of
+/// the 1000 closest pairs for part A, and binary search for the shortest prefix of pairs that
+/// connects all junction boxes for part B.
+pub fn solve_reference(lines: &[String]) -> Solution {
+    let points: Vec<(i64, i64, i64)> = lines
         .iter()
         .map(|line| line.trim())
         .filter(|line| !line.is_empty())
         .map(|line| {
-            let (x, rest) = line.split_once(',').unwrap();
-            let (y, z) = rest.split_once(',').unwrap();
-            Point(x.parse().unwrap(), y.parse().unwrap(), z.parse().unwrap())
+            let coords: Vec<i64> = line.split(',').map(|s| s.parse().unwrap()).collect();
+            (coords[0], coords[1], coords[2])
         })
         .collect();
+    let dist2 = |i: usize, j: usize| {
+        let (x1, y1, z1) = points[i];
+        let (x2, y2, z2) = points[j];
+        (x1 - x2).pow(2) + (y1 - y2).pow(2) + (z1 - z2).pow(2)
+    };
+    let mut pairs: Vec<(usize, usize)> = (0..points.len())
+        .flat_map(|i| ((i + 1)..points.len()).map(move |j| (i, j)))
+        .collect();
+    pairs.sort_by_key(|(i, j)| (dist2(*i, *j), *i, *j));
 
-    let by_dist: Vec<(usize, usize)> = {
-        let by_dist: Vec<(usize, usize)> = points
-            .iter()
-            .enumerate()
-            .flat_map(|(ip, _)| {
-                points
-                    .iter()
-                    .enumerate()
-                    .skip(ip + 1)
-                    .map(move |(iq, _)| (ip, iq))
-            })
-            .collect();
-        let mut by_dist: Vec<(usize, usize)> = by_dist.into_iter().collect();
-        by_dist.sort_by_key(|(ip, iq)| points[*ip].dist2(&points[*iq]));
-        by_dist
+    let component_sizes = |edges: &[(usize, usize)]| -> Vec<usize> {
+        let mut neighbors: Vec<Vec<usize>> = vec![Vec::new(); points.len()];
+        for (p, q) in edges {
+            neighbors[*p].push(*q);
+            neighbors[*q].push(*p);
+        }
+        let mut seen = vec![false; points.len()];
+        let mut sizes = Vec::new();
+        for root in 0..points.len() {
+            if !seen[root] {
+                seen[root] = true;
+                let mut stack = vec![root];
+                let mut size = 0;
+                while let Some(i) = stack.pop() {
+                    size += 1;
+                    for j in &neighbors[i] {
+                        if !seen[*j] {
+                            seen[*j] = true;
+                            stack.push(*j);
+                        }
+                    }
+                }
+                sizes.push(size);
+            }
+        }
+        sizes
     };
 
-    let (sol_a, sol_b) = solve_ab(&points, &by_dist);
+    let mut sizes = component_sizes(&pairs[..std::cmp::min(1000, pairs.len())]);
+    sizes.sort();
+    let sol_a: usize = sizes.into_iter().rev().take(3).product();
+
+    let connected_at = (0..pairs.len())
+        .collect::<Vec<usize>>()
+        .partition_point(|k| component_sizes(&pairs[..=*k]).len() > 1);
+    let (i, j) = pairs[connected_at];
+    let sol_b = points[i].0 * points[j].0;
+
     (sol_a.to_string(), sol_b.to_string())
 }
@@ -14,13 +14,30 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use crate::common::nonempty_lines;
+use crate::common::parse_number;
+use crate::common::ParseError;
 use crate::common::Solution;
+use crate::render::Drawing;
+use crate::render::Shape;
+use crate::render::BLUE;
+use crate::render::GREEN;
+use crate::render::RED;
+use crate::render::WHITE;
+
+/// Bound on coordinates so that areas and doubled coordinates cannot overflow.
+const MAX_COORDINATE: i64 = 1_000_000_000;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
-struct Point {
+pub struct Point {
     x: i64,
     y: i64,
 }
+impl std::fmt::Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
 impl Point {
     fn area(&self, other: &Self) -> u64 {
         (1 + self.x.abs_diff(other.x)) * (1 + self.y.abs_diff(other.y))
@@ -55,17 +72,37 @@ fn intersects_interior(line: &Line, (rp, rq): (&Point, &Point)) -> bool {
     }
 }
 
-fn solve_a(points: &[Point]) -> u64 {
+/// Whether the point at half-tile coordinates `(x2 / 2, y2 / 2)` is on or inside the loop.
+fn contains_doubled(lines: &[Line], (x2, y2): (i64, i64)) -> bool {
+    let on_line = |line: &Line| {
+        let (lminx, lmaxx) = minmax(2 * line.p.x, 2 * line.q.x);
+        let (lminy, lmaxy) = minmax(2 * line.p.y, 2 * line.q.y);
+        (lminx..=lmaxx).contains(&x2) && (lminy..=lmaxy).contains(&y2)
+    };
+    let crosses_ray = |line: &Line| {
+        let (lminy, lmaxy) = minmax(2 * line.p.y, 2 * line.q.y);
+        line.p.x == line.q.x && 2 * line.p.x > x2 && (lminy..lmaxy).contains(&y2)
+    };
+    lines.iter().any(on_line) || lines.iter().filter(|line| crosses_ray(line)).count() % 2 == 1
+}
+
+/// The opposite red corners of the largest rectangle.
+fn largest_rectangle(points: &[Point]) -> (Point, Point) {
     points
         .iter()
         .enumerate()
-        .flat_map(|(ip, p)| points.iter().skip(ip + 1).map(move |q| (p, q)))
-        .map(|(p, q)| p.area(q))
-        .max()
+        .flat_map(|(ip, p)| points.iter().skip(ip + 1).map(move |q| (*p, *q)))
+        .max_by_key(|(p, q)| p.area(q))
         .unwrap()
 }
 
-fn solve_b(points: &[Point]) -> u64 {
+pub fn solve_a(points: &[Point]) -> u64 {
+    let (p, q) = largest_rectangle(points);
+    p.area(&q)
+}
+
+/// The opposite red corners of the largest rectangle of red and green tiles.
+fn best_rectangle(points: &[Point]) -> Option<(Point, Point)> {
     let lines: Vec<Line> = points
         .windows(2)
         .flat_map(|window| {
@@ -85,38 +122,235 @@ fn solve_b(points: &[Point]) -> u64 {
         .iter()
         .enumerate()
         .flat_map(|(ip, p)| points.iter().skip(ip + 1).map(move |q| (p, q)))
-        .fold(0, |max, (p, q)| {
+        .fold((0, None), |(max, best), (p, q)| {
             let a = p.area(q);
-            if a > max && !lines.iter().any(|line| intersects_interior(line, (p, q))) {
-                a
+            if a > max
+                && !lines.iter().any(|line| intersects_interior(line, (p, q)))
+                && contains_doubled(&lines, (p.x + q.x, p.y + q.y))
+            {
+                (a, Some((*p, *q)))
+            } else {
+                (max, best)
+            }
+        })
+        .1
+}
+
+pub fn solve_b(points: &[Point]) -> u64 {
+    best_rectangle(points).map(|(p, q)| p.area(&q)).unwrap_or(0)
+}
+
+/// The corners of the rectangles of parts A and B.
+pub fn explain(lines: &[String]) -> Vec<String> {
+    let points = parse(lines).unwrap_or_else(|e| panic!("{e}"));
+    let (p, q) = largest_rectangle(&points);
+    let mut explanation = vec![format!("A: corners {p} and {q}, area {}", p.area(&q))];
+    explanation.push(match best_rectangle(&points) {
+        Some((p, q)) => format!("B: corners {p} and {q}, area {}", p.area(&q)),
+        None => "B: no rectangle inside the loop".to_string(),
+    });
+    explanation
+}
+
+/// The loop of red and green tiles, the best rectangle of part B in blue, and the red tiles.
+pub fn render(lines: &[String]) -> Drawing {
+    let points = parse(lines).unwrap_or_else(|e| panic!("{e}"));
+    // Coordinates are at most MAX_COORDINATE in magnitude, so they fit in an i32.
+    let coordinate = |c: i64| f64::from(i32::try_from(c).expect("Coordinate out of range"));
+    let min_x = points.iter().map(|p| p.x).min().unwrap();
+    let max_x = points.iter().map(|p| p.x).max().unwrap();
+    let min_y = points.iter().map(|p| p.y).min().unwrap();
+    let max_y = points.iter().map(|p| p.y).max().unwrap();
+    // Place the top left tile at the origin, so that tiles at negative coordinates are drawn too.
+    let at = |x: i64, y: i64| {
+        (
+            coordinate(x) - coordinate(min_x),
+            coordinate(y) - coordinate(min_y),
+        )
+    };
+    let (width, height) = at(max_x + 1, max_y + 1);
+    let mut drawing = Drawing::new(width, height, WHITE);
+    let center = |p: &Point| {
+        let (x, y) = at(p.x, p.y);
+        (x + 0.5, y + 0.5)
+    };
+    drawing.push(Shape::Polygon {
+        points: points.iter().map(center).collect(),
+        fill: GREEN,
+        stroke: GREEN,
+    });
+    if let Some((p, q)) = best_rectangle(&points) {
+        let (x, y) = at(p.x.min(q.x), p.y.min(q.y));
+        let (right, bottom) = at(p.x.max(q.x) + 1, p.y.max(q.y) + 1);
+        drawing.push(Shape::Rect {
+            x,
+            y,
+            width: right - x,
+            height: bottom - y,
+            fill: BLUE,
+        });
+    }
+    let radius = (width.max(height) / 400.0).max(0.5);
+    for p in &points {
+        let (x, y) = center(p);
+        drawing.push(Shape::Circle {
+            x,
+            y,
+            radius,
+            fill: RED,
+        });
+    }
+    drawing
+}
+
+/// The red tiles in order around the loop. Consecutive tiles are on the same row or column, and
+/// no three consecutive tiles are.
+pub fn parse(lines: &[String]) -> Result<Vec<Point>, ParseError> {
+    let points: Vec<(usize, Point)> = nonempty_lines(lines)
+        .map(|(l, line)| {
+            let (x, y) = line
+                .split_once(',')
+                .ok_or_else(|| ParseError::new(l, format!("Invalid tile: {line:?}")))?;
+            let p = Point {
+                x: parse_number(l, x)?,
+                y: parse_number(l, y)?,
+            };
+            let range = -MAX_COORDINATE..=MAX_COORDINATE;
+            if range.contains(&p.x) && range.contains(&p.y) {
+                Ok((l, p))
             } else {
-                max
+                Err(ParseError::new(l, "Coordinate out of range"))
             }
         })
+        .collect::<Result<_, _>>()?;
+
+    if points.len() < 2 {
+        return Err(ParseError::input("Expected at least 2 red tiles"));
+    }
+    for (i, (l, p)) in points.iter().enumerate() {
+        let (_, q) = points[(i + 1) % points.len()];
+        if p.x != q.x && p.y != q.y {
+            return Err(ParseError::new(
+                *l,
+                "Expected the next tile on the same row or column",
+            ));
+        }
+    }
+    if let Some(window) = points.windows(3).find(|window| {
+        let [(_, p), _, (_, r)] = window else {
+            unreachable!()
+        };
+        p.x == r.x || p.y == r.y
+    }) {
+        return Err(ParseError::new(
+            window[1].0,
+            "Expected no straight lines between triples of red tiles",
+        ));
+    }
+
+    Ok(points.into_iter().map(|(_, p)| p).collect())
 }
 
 pub fn solve(lines: &[String]) -> Solution {
-    let points: Vec<Point> = lines
+    let points = parse(lines).unwrap_or_else(|e| panic!("{e}"));
+    (solve_a(&points).to_string(), solve_b(&points).to_string())
+}
+
+/// Reference implementation: compress the floor into blocks of tiles that are either all inside
+/// or all outside the loop, classify each block by ray casting, and check every block of every
+/// candidate rectangle.
+pub fn solve_reference(lines: &[String]) -> Solution {
+    let points: Vec<(i64, i64)> = lines
         .iter()
         .map(|line| line.trim())
         .filter(|line| !line.is_empty())
         .map(|line| {
             let (x, y) = line.split_once(',').unwrap();
-            Point {
-                x: x.parse().unwrap(),
-                y: y.parse().unwrap(),
-            }
+            (x.parse().unwrap(), y.parse().unwrap())
         })
         .collect();
-
-    debug_assert!(
-        points.windows(3).all(|window| if let [p, _, r] = window {
-            p.x != r.x && p.y != r.y
-        } else {
-            false
+    let edges: Vec<((i64, i64), (i64, i64))> = (0..points.len())
+        .map(|i| (points[i], points[(i + 1) % points.len()]))
+        .collect();
+    assert!(
+        edges.iter().all(|(p, q)| p.0 == q.0 || p.1 == q.1),
+        "Expected red tiles to be connected by straight lines"
+    );
+    assert!(
+        (0..points.len()).all(|i| {
+            let (p, r) = (points[i], points[(i + 2) % points.len()]);
+            p.0 != r.0 && p.1 != r.1
         }),
         "Expected no straight lines between triples of red tiles"
     );
+    let area =
+        |(x1, y1): (i64, i64), (x2, y2): (i64, i64)| (1 + x1.abs_diff(x2)) * (1 + y1.abs_diff(y2));
 
-    (solve_a(&points).to_string(), solve_b(&points).to_string())
+    let sol_a = (0..points.len())
+        .flat_map(|i| (i..points.len()).map(move |j| (i, j)))
+        .map(|(i, j)| area(points[i], points[j]))
+        .max()
+        .unwrap();
+
+    // Each axis is split into single red tile coordinates and the gaps between them.
+    let spans = |mut coords: Vec<i64>| -> Vec<(i64, i64)> {
+        coords.sort();
+        coords.dedup();
+        coords
+            .iter()
+            .enumerate()
+            .flat_map(|(i, v)| {
+                std::iter::once((*v, *v)).chain(
+                    coords
+                        .get(i + 1)
+                        .filter(|next| **next > v + 1)
+                        .map(|next| (v + 1, next - 1)),
+                )
+            })
+            .collect()
+    };
+    let xs = spans(points.iter().map(|(x, _)| *x).collect());
+    let ys = spans(points.iter().map(|(_, y)| *y).collect());
+
+    let on_loop = |x: i64, y: i64| {
+        edges.iter().any(|((x1, y1), (x2, y2))| {
+            (*x1.min(x2)..=*x1.max(x2)).contains(&x) && (*y1.min(y2)..=*y1.max(y2)).contains(&y)
+        })
+    };
+    let inside_loop = |x: i64, y: i64| {
+        edges
+            .iter()
+            .filter(|((x1, y1), (x2, y2))| {
+                x1 == x2 && *x1 > x && (*y1.min(y2)..*y1.max(y2)).contains(&y)
+            })
+            .count()
+            % 2
+            == 1
+    };
+    let allowed: Vec<Vec<bool>> = xs
+        .iter()
+        .map(|(x, _)| {
+            ys.iter()
+                .map(|(y, _)| on_loop(*x, *y) || inside_loop(*x, *y))
+                .collect()
+        })
+        .collect();
+    let x_index = |x: i64| xs.binary_search(&(x, x)).unwrap();
+    let y_index = |y: i64| ys.binary_search(&(y, y)).unwrap();
+
+    let sol_b = (0..points.len())
+        .flat_map(|i| (i..points.len()).map(move |j| (i, j)))
+        .map(|(i, j)| (points[i], points[j]))
+        .fold(0, |best, (p, q)| {
+            let a = area(p, q);
+            let (xi, xj) = minmax(x_index(p.0), x_index(q.0));
+            let (yi, yj) = minmax(y_index(p.1), y_index(q.1));
+            if a > best && (xi..=xj).all(|x| (yi..=yj).all(|y| allowed[x][y])) {
+                a
+            } else {
+                best
+            }
+        });
+
+    (sol_a.to_string(), sol_b.to_string())
 }
@@ -14,86 +14,401 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+
+use crate::common::nonempty_lines;
+use crate::common::parse_number;
+use crate::common::trace;
+use crate::common::ParseError;
 use crate::common::Solution;
+use crate::util::gf2::BitMatrix;
+use crate::util::gf2::BitVector;
+use crate::util::linalg::Rref;
+
+/// The most buttons on a machine. Part A enumerates up to 2^n combinations of n buttons, so this
+/// keeps it to about a million per machine. Machines in the puzzle input have far fewer buttons.
+const MAX_BUTTONS: usize = 20;
 
 #[derive(Debug, Eq, PartialEq)]
-struct Machine {
+pub struct Machine {
     lights: Vec<bool>,
     buttons: Vec<Vec<usize>>,
-    joltage: Vec<i64>,
+    joltage: Vec<u16>,
+}
+impl Machine {
+    /// The lights to turn on, one bit per light.
+    fn light_mask(&self) -> u64 {
+        self.lights
+            .iter()
+            .enumerate()
+            .map(|(i, b)| if *b { 1 << i } else { 0 })
+            .sum()
+    }
+
+    /// The lights toggled by each button, one bit per light.
+    fn button_masks(&self) -> Vec<u64> {
+        self.buttons
+            .iter()
+            .map(|lights| lights.iter().map(|i| 1 << i).sum())
+            .collect()
+    }
 }
-impl Machine {}
 
 fn fewest_presses(lights: u64, buttons: &[u64]) -> Option<usize> {
-    if lights == 0 {
-        Some(0)
-    } else if buttons.is_empty() {
-        None
-    } else {
-        [
-            fewest_presses(lights ^ buttons[0], &buttons[1..]).map(|i| i + 1),
-            fewest_presses(lights, &buttons[1..]),
-        ]
+    pressed_buttons(lights, buttons).map(|pressed| pressed.len())
+}
+
+/// The equations over GF(2) for the presses that turn on `lights`: one row per light, with a bit
+/// for each button that toggles it.
+fn toggle_equations(lights: u64, buttons: &[u64]) -> (BitMatrix, BitVector) {
+    let num_lights = (64
+        - buttons
+            .iter()
+            .fold(lights, |all, b| all | b)
+            .leading_zeros()) as usize;
+    let mut toggles = BitMatrix::new(buttons.len());
+    for light in 0..num_lights {
+        toggles.push_row(BitVector::from_ones(
+            buttons.len(),
+            (0..buttons.len()).filter(|bi| buttons[*bi] & (1 << light) != 0),
+        ));
+    }
+    let target = BitVector::from_ones(
+        num_lights,
+        (0..num_lights).filter(|light| lights & (1 << light) != 0),
+    );
+    (toggles, target)
+}
+
+/// The indices of a fewest buttons to press to turn on `lights`. Pressing a button twice undoes
+/// it, so the presses solve the toggle equations over GF(2). Every solution is one particular
+/// solution plus a combination of the null space, so only the null space needs enumerating.
+fn pressed_buttons(lights: u64, buttons: &[u64]) -> Option<Vec<usize>> {
+    let (toggles, target) = toggle_equations(lights, buttons);
+    let mut presses = toggles.solve(&target)?;
+    let null_space = toggles.null_space();
+    let mut best = presses.clone();
+    // Visit every combination of the null space in Gray code order, one XOR per step. Its
+    // dimension is at most MAX_BUTTONS, so there are at most 2^MAX_BUTTONS combinations.
+    for i in 1_u64..(1 << null_space.len()) {
+        presses ^= &null_space[i.trailing_zeros() as usize];
+        if presses.count_ones() < best.count_ones() {
+            best = presses.clone();
+        }
+    }
+    Some(best.ones().collect())
+}
+
+/// Breadth-first search over light states, which scales with the number of lights rather than
+/// the number of buttons.
+fn fewest_presses_bfs(lights: u64, buttons: &[u64]) -> Option<usize> {
+    let mut dist: HashMap<u64, usize> = HashMap::new();
+    let mut queue: VecDeque<u64> = VecDeque::new();
+    dist.insert(0, 0);
+    queue.push_back(0);
+    while let Some(state) = queue.pop_front() {
+        let d = dist[&state];
+        if state == lights {
+            return Some(d);
+        }
+        for button in buttons {
+            let next = state ^ button;
+            if let Entry::Vacant(entry) = dist.entry(next) {
+                entry.insert(d + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+fn solve_a_with(machines: &[Machine], fewest_presses: fn(u64, &[u64]) -> Option<usize>) -> usize {
+    machines
+        .iter()
+        .enumerate()
+        .map(|(i, machine)| {
+            let lights = machine.light_mask();
+            let presses = fewest_presses(lights, &machine.button_masks())
+                .unwrap_or_else(|| panic!("Machine {}: lights should be checked in parse", i + 1));
+            trace!("Lights {lights:#b}: {presses} presses");
+            presses
+        })
+        .sum()
+}
+
+/// The joltage equations of `machine`: one row per counter, with a 1 for each button that
+/// increments it.
+fn joltage_equations(machine: &Machine) -> (Vec<Vec<i64>>, Vec<i64>) {
+    let a: Vec<Vec<i64>> = (0..machine.joltage.len())
+        .map(|counter| {
+            machine
+                .buttons
+                .iter()
+                .map(|button| i64::from(button.contains(&counter)))
+                .collect()
+        })
+        .collect();
+    let b: Vec<i64> = machine.joltage.iter().copied().map(i64::from).collect();
+    (a, b)
+}
+
+/// The presses of each button in a fewest presses that bring every counter to its joltage. The
+/// joltage equations, one per counter, are reduced to row echelon form, and then the presses of
+/// the free buttons are searched by branch and bound. No button is pressed more times than the
+/// lowest joltage it contributes to.
+fn fewest_presses_joltage(machine: &Machine) -> Option<Vec<u64>> {
+    let (a, b) = joltage_equations(machine);
+    let bounds: Vec<i64> = machine
+        .buttons
+        .iter()
+        .map(|button| {
+            button
+                .iter()
+                .map(|counter| i64::from(machine.joltage[*counter]))
+                .min()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    Rref::new(&a, &b)?
+        .min_sum_solution(&bounds)?
         .into_iter()
-        .flatten()
-        .min()
+        .map(|p| u64::try_from(p).ok())
+        .collect()
+}
+
+fn parse_machine(l: usize, line: &str) -> Result<Machine, ParseError> {
+    let invalid = || ParseError::new(l, format!("Invalid machine: {line:?}"));
+    let line = line.strip_prefix('[').ok_or_else(invalid)?;
+    let (lights, line) = line.split_once(']').ok_or_else(invalid)?;
+    let (buttons, joltage) = line.split_once('{').ok_or_else(invalid)?;
+    let joltage = joltage.trim().strip_suffix('}').ok_or_else(invalid)?;
+
+    let lights: Vec<bool> = lights
+        .chars()
+        .map(|ch| match ch {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(ParseError::new(l, format!("Invalid light: {ch:?}"))),
+        })
+        .collect::<Result<_, _>>()?;
+    if lights.is_empty() || lights.len() > 64 {
+        return Err(ParseError::new(l, "Expected between 1 and 64 lights"));
     }
+
+    let buttons: Vec<Vec<usize>> = buttons
+        .split_ascii_whitespace()
+        .map(|button| {
+            let button = button
+                .strip_prefix('(')
+                .and_then(|button| button.strip_suffix(')'))
+                .ok_or_else(|| ParseError::new(l, format!("Invalid button: {button:?}")))?;
+            button
+                .split(',')
+                .map(|i| {
+                    let i: usize = parse_number(l, i)?;
+                    if i < lights.len() {
+                        Ok(i)
+                    } else {
+                        Err(ParseError::new(l, format!("No light {i}")))
+                    }
+                })
+                .collect()
+        })
+        .collect::<Result<_, _>>()?;
+    if buttons.len() > MAX_BUTTONS {
+        return Err(ParseError::new(
+            l,
+            format!("Expected at most {MAX_BUTTONS} buttons"),
+        ));
+    }
+
+    let joltage: Vec<u16> = joltage
+        .split(',')
+        .map(|j| parse_number(l, j))
+        .collect::<Result<_, _>>()?;
+    if joltage.len() != lights.len() {
+        return Err(ParseError::new(
+            l,
+            format!("Expected {} joltage requirements", lights.len()),
+        ));
+    }
+
+    let machine = Machine {
+        lights,
+        buttons,
+        joltage,
+    };
+    let (toggles, target) = toggle_equations(machine.light_mask(), &machine.button_masks());
+    if toggles.solve(&target).is_none() {
+        return Err(ParseError::new(l, "No presses turn on the lights"));
+    }
+    // Only a search for whole numbers of presses can tell whether there is any, so this costs as
+    // much as solving part B.
+    if fewest_presses_joltage(&machine).is_none() {
+        return Err(ParseError::new(l, "No presses reach the joltage"));
+    }
+    Ok(machine)
+}
+
+pub fn parse(lines: &[String]) -> Result<Vec<Machine>, ParseError> {
+    nonempty_lines(lines)
+        .map(|(l, line)| parse_machine(l, line))
+        .collect()
+}
+
+pub fn solve_a(machines: &[Machine]) -> usize {
+    solve_a_with(machines, fewest_presses)
 }
 
-fn solve_a(machines: &[Machine]) -> usize {
+pub fn solve_b(machines: &[Machine]) -> u64 {
     machines
         .iter()
-        .flat_map(
-            |Machine {
-                 lights, buttons, ..
-             }| {
-                let lights: u64 = lights
-                    .iter()
-                    .enumerate()
-                    .map(|(i, b)| if *b { 1 << i } else { 0 })
-                    .sum();
-                let buttons: Vec<u64> = buttons
-                    .iter()
-                    .map(|lights| lights.iter().map(|i| 1 << i).sum())
-                    .collect();
-                fewest_presses(lights, &buttons)
-            },
-        )
+        .enumerate()
+        .map(|(i, machine)| {
+            fewest_presses_joltage(machine)
+                .unwrap_or_else(|| panic!("Machine {}: joltage should be checked in parse", i + 1))
+                .into_iter()
+                .sum::<u64>()
+        })
         .sum()
 }
 
+fn solve_with(lines: &[String], fewest_presses: fn(u64, &[u64]) -> Option<usize>) -> Solution {
+    let machines = parse(lines).unwrap_or_else(|e| panic!("{e}"));
+    (
+        solve_a_with(&machines, fewest_presses).to_string(),
+        solve_b(&machines).to_string(),
+    )
+}
+
 pub fn solve(lines: &[String]) -> Solution {
-    let machines: Vec<Machine> = lines
+    solve_with(lines, fewest_presses)
+}
+
+pub fn solve_bfs(lines: &[String]) -> Solution {
+    solve_with(lines, fewest_presses_bfs)
+}
+
+/// The buttons pressed on each machine in part A.
+pub fn explain(lines: &[String]) -> Vec<String> {
+    let machines = parse(lines).unwrap_or_else(|e| panic!("{e}"));
+    machines
+        .iter()
+        .enumerate()
+        .map(
+            |(i, machine)| match pressed_buttons(machine.light_mask(), &machine.button_masks()) {
+                Some(pressed) => format!(
+                    "Machine {}: press {}",
+                    i + 1,
+                    pressed
+                        .into_iter()
+                        .map(|bi| {
+                            let lights: Vec<String> =
+                                machine.buttons[bi].iter().map(usize::to_string).collect();
+                            format!("({})", lights.join(","))
+                        })
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                ),
+                None => format!("Machine {}: cannot be configured", i + 1),
+            },
+        )
+        .collect()
+}
+
+/// The fewest presses that bring the counters from 0 to `joltage`. Every solution presses some
+/// subset of buttons an odd number of times and the rest an even number of times, so try every
+/// subset whose presses leave even joltages, and halve the rest of the problem.
+fn fewest_presses_halving(
+    joltage: &[u32],
+    buttons: &[Vec<usize>],
+    memo: &mut HashMap<Vec<u32>, Option<u32>>,
+) -> Option<u32> {
+    if joltage.iter().all(|j| *j == 0) {
+        return Some(0);
+    }
+    if let Some(presses) = memo.get(joltage) {
+        return *presses;
+    }
+    let mut best = None;
+    for pressed in 0_u64..(1 << buttons.len()) {
+        let mut rest = joltage.to_vec();
+        let mut valid = true;
+        for (bi, button) in buttons.iter().enumerate() {
+            if pressed & (1 << bi) != 0 {
+                for i in button {
+                    valid &= rest[*i] > 0;
+                    rest[*i] = rest[*i].saturating_sub(1);
+                }
+            }
+        }
+        if valid && rest.iter().all(|j| j % 2 == 0) {
+            let half: Vec<u32> = rest.iter().map(|j| j / 2).collect();
+            if let Some(presses) = fewest_presses_halving(&half, buttons, memo) {
+                let presses = pressed.count_ones() + 2 * presses;
+                best = Some(best.map_or(presses, |best: u32| best.min(presses)));
+            }
+        }
+    }
+    memo.insert(joltage.to_vec(), best);
+    best
+}
+
+/// Reference implementation: try every subset of buttons for part A, and for part B recurse on
+/// the parity of the joltages.
+pub fn solve_reference(lines: &[String]) -> Solution {
+    let (sol_a, sol_b) = lines
         .iter()
         .map(|line| line.trim())
         .filter(|line| !line.is_empty())
-        .flat_map(|line| {
-            let line = line.strip_prefix("[")?;
-            let (lights, line) = line.split_once(']')?;
-            let (buttons, joltage) = line.split_once('{')?;
-            let joltage = joltage.trim().strip_suffix("}")?;
-            Some(Machine {
-                lights: lights.trim().chars().map(|ch| ch == '#').collect(),
-                buttons: buttons
-                    .trim()
-                    .split_ascii_whitespace()
-                    .flat_map(|btn| {
-                        Some(
-                            btn.strip_prefix("(")?
-                                .strip_suffix(")")?
-                                .split(',')
-                                .flat_map(|digits| digits.parse().ok())
-                                .collect::<Vec<_>>(),
-                        )
-                    })
-                    .collect(),
-                joltage: joltage
-                    .split(',')
-                    .flat_map(|digits| digits.parse().ok())
-                    .collect(),
-            })
-        })
-        .collect();
+        .map(|line| {
+            let mut parts = line.split_ascii_whitespace();
+            let lights: Vec<bool> = parts
+                .next()
+                .unwrap()
+                .trim_matches(['[', ']'])
+                .chars()
+                .map(|ch| ch == '#')
+                .collect();
+            let parts: Vec<&str> = parts.collect();
+            let (joltage, buttons) = parts.split_last().unwrap();
+            let buttons: Vec<Vec<usize>> = buttons
+                .iter()
+                .map(|part| {
+                    part.trim_matches(['(', ')'])
+                        .split(',')
+                        .map(|i| i.parse().unwrap())
+                        .collect()
+                })
+                .collect();
+            let joltage: Vec<u32> = joltage
+                .trim_matches(['{', '}'])
+                .split(',')
+                .map(|j| j.parse().unwrap())
+                .collect();
 
-    (solve_a(&machines).to_string(), "".to_string())
+            let presses_a = (0_u64..(1 << buttons.len()))
+                .filter(|pressed| {
+                    let mut state = vec![false; lights.len()];
+                    for (bi, button) in buttons.iter().enumerate() {
+                        if pressed & (1 << bi) != 0 {
+                            for i in button {
+                                state[*i] = !state[*i];
+                            }
+                        }
+                    }
+                    state == lights
+                })
+                .map(|pressed| pressed.count_ones())
+                .min()
+                .unwrap();
+            let presses_b =
+                fewest_presses_halving(&joltage, &buttons, &mut HashMap::new()).unwrap_or(0);
+            (presses_a, presses_b)
+        })
+        .fold((0, 0), |(sol_a, sol_b), (a, b)| (sol_a + a, sol_b + b));
+    (sol_a.to_string(), sol_b.to_string())
 }
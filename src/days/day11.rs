@@ -14,37 +14,209 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::HashMap;
 
+use crate::common::nonempty_lines;
+use crate::common::ParseError;
 use crate::common::Solution;
+use crate::util::graph::Graph;
+use crate::util::graph::NodeId;
+use crate::util::graph::PathCountError;
 
-fn solve_a(graph: &HashMap<&str, HashSet<&str>>) -> usize {
-    let mut paths = HashMap::new();
-    paths.insert("you", 1);
-    let mut queue = VecDeque::new();
-    queue.push_back("you");
-    while let Some(next) = queue.pop_front() {
-        if let Some(neighbors) = graph.get(next) {
-            for neighbor in neighbors {
-                *paths.entry(neighbor).or_insert(0) += 1;
-                queue.push_back(neighbor);
-            }
+/// The devices that the paths counted in part A visit in order.
+const ROUTE_A: [&str; 2] = ["you", "out"];
+
+/// There are no cycles, so every path through both `dac` and `fft` visits them in one of two
+/// orders. Part B counts the paths along either route.
+const ROUTES_B: [[&str; 4]; 2] = [["svr", "dac", "fft", "out"], ["svr", "fft", "dac", "out"]];
+
+/// The number of paths that visit the devices of `route` in order. Each leg starts at a device
+/// reachable from the first one, so there is no cycle to count around if the first has none.
+fn count_paths_along(graph: &Graph, route: &[&str]) -> Result<u128, PathCountError> {
+    let mut count: u128 = 1;
+    for leg in route.windows(2) {
+        let (Some(from), Some(to)) = (graph.id(leg[0]), graph.id(leg[1])) else {
+            return Ok(0);
+        };
+        if count == 0 {
+            return Ok(0);
+        }
+        count = count
+            .checked_mul(graph.count_paths(from, to)?)
+            .ok_or(PathCountError::Overflow)?;
+    }
+    Ok(count)
+}
+
+fn count_paths_b(graph: &Graph) -> Result<u128, PathCountError> {
+    ROUTES_B.iter().try_fold(0_u128, |total, route| {
+        total
+            .checked_add(count_paths_along(graph, route)?)
+            .ok_or(PathCountError::Overflow)
+    })
+}
+
+pub fn solve_a(graph: &Graph) -> u128 {
+    count_paths_along(graph, &ROUTE_A).expect("Path count should be checked in parse")
+}
+
+pub fn solve_b(graph: &Graph) -> u128 {
+    count_paths_b(graph).expect("Path count should be checked in parse")
+}
+
+/// The outputs of each device. Fails if a cycle is reachable from `you` or `svr`, since then
+/// there would be infinitely many paths, or if there are too many paths to count.
+pub fn parse(lines: &[String]) -> Result<Graph, ParseError> {
+    let mut graph = Graph::new();
+    let mut device_lines = HashMap::new();
+    for (l, line) in nonempty_lines(lines) {
+        let (key, rest) = line
+            .split_once(':')
+            .ok_or_else(|| ParseError::new(l, format!("Invalid device: {line:?}")))?;
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            return Err(ParseError::new(l, format!("Invalid device name: {key:?}")));
+        }
+        let device = graph.intern(key);
+        if device_lines.insert(device, l).is_some() {
+            return Err(ParseError::new(l, format!("Duplicate device: {key:?}")));
+        }
+        let mut outputs: Vec<&str> = rest.split_ascii_whitespace().collect();
+        outputs.sort();
+        outputs.dedup();
+        for output in outputs {
+            let output = graph.intern(output);
+            graph.add_edge(device, output);
+        }
+    }
+    for start in ["you", "svr"] {
+        if let Some(Err(device)) = graph
+            .id(start)
+            .map(|start| graph.topological_order_from(start))
+        {
+            return Err(ParseError::new(
+                device_lines[&device],
+                format!(
+                    "Cycle through device {:?} reachable from {start:?}",
+                    graph.name(device)
+                ),
+            ));
+        }
+    }
+    for (start, count) in [
+        ("you", count_paths_along(&graph, &ROUTE_A)),
+        ("svr", count_paths_b(&graph)),
+    ] {
+        if count.is_err() {
+            return Err(ParseError::input(format!(
+                "Too many paths from {start:?} to \"out\" to count"
+            )));
         }
     }
-    paths["out"]
+    Ok(graph)
 }
 
 pub fn solve(lines: &[String]) -> Solution {
-    let graph: HashMap<&str, HashSet<&str>> = lines
+    let graph = parse(lines).unwrap_or_else(|e| panic!("{e}"));
+    (solve_a(&graph).to_string(), solve_b(&graph).to_string())
+}
+
+/// The most paths `explain` lists. There can be exponentially many, so the rest are only counted.
+const MAX_EXPLAINED_PATHS: usize = 100;
+
+/// The first `limit` paths from `from` to `to`, taking outputs in order of name. Only devices
+/// that lead to `to` are visited, so each path listed costs time in proportion to its length.
+fn paths(graph: &Graph, from: NodeId, to: NodeId, limit: usize) -> Vec<Vec<&str>> {
+    let order = graph
+        .topological_order_from(from)
+        .expect("Cycles should be checked in parse");
+    let mut leads_to = vec![false; graph.len()];
+    for node in order.into_iter().rev() {
+        leads_to[node] = node == to || graph.neighbors(node).iter().any(|next| leads_to[*next]);
+    }
+    let mut found = Vec::new();
+    if leads_to[from] {
+        extend_paths(graph, &leads_to, to, &mut vec![from], &mut found, limit);
+    }
+    found
+}
+
+/// Adds the paths that continue `path` to `to` onto `found` until there are `limit` of them.
+fn extend_paths<'a>(
+    graph: &'a Graph,
+    leads_to: &[bool],
+    to: NodeId,
+    path: &mut Vec<NodeId>,
+    found: &mut Vec<Vec<&'a str>>,
+    limit: usize,
+) {
+    let node = path[path.len() - 1];
+    if node == to {
+        found.push(path.iter().map(|n| graph.name(*n)).collect());
+        return;
+    }
+    let mut nexts: Vec<NodeId> = graph
+        .neighbors(node)
+        .iter()
+        .copied()
+        .filter(|next| leads_to[*next])
+        .collect();
+    nexts.sort_by_key(|next| graph.name(*next));
+    for next in nexts {
+        if found.len() == limit {
+            return;
+        }
+        path.push(next);
+        extend_paths(graph, leads_to, to, path, found, limit);
+        path.pop();
+    }
+}
+
+/// The paths counted in part A, up to `MAX_EXPLAINED_PATHS` of them and then the number left.
+pub fn explain(lines: &[String]) -> Vec<String> {
+    let graph = parse(lines).unwrap_or_else(|e| panic!("{e}"));
+    let (Some(you), Some(out)) = (graph.id("you"), graph.id("out")) else {
+        return Vec::new();
+    };
+    let listed = paths(&graph, you, out, MAX_EXPLAINED_PATHS);
+    let rest = solve_a(&graph) - u128::try_from(listed.len()).unwrap();
+    let mut explanation: Vec<String> = listed.into_iter().map(|path| path.join(" -> ")).collect();
+    if rest > 0 {
+        explanation.push(format!("... and {rest} more paths"));
+    }
+    explanation
+}
+
+/// The number of paths from `from` to `to` that pass through every node in `via`.
+fn count_paths(graph: &HashMap<&str, Vec<&str>>, from: &str, to: &str, via: &[&str]) -> usize {
+    let via: Vec<&str> = via.iter().copied().filter(|v| *v != from).collect();
+    if from == to {
+        usize::from(via.is_empty())
+    } else {
+        graph
+            .get(from)
+            .map(|neighbors| {
+                neighbors
+                    .iter()
+                    .map(|next| count_paths(graph, next, to, &via))
+                    .sum()
+            })
+            .unwrap_or(0)
+    }
+}
+
+/// Reference implementation: enumerate every path by depth-first search.
+pub fn solve_reference(lines: &[String]) -> Solution {
+    let graph: HashMap<&str, Vec<&str>> = lines
         .iter()
         .map(|line| line.trim())
         .filter(|line| !line.is_empty())
-        .filter_map(|line| {
-            let (key, rest) = line.split_once(':')?;
-            let neighbors = rest.trim().split_ascii_whitespace().collect();
-            Some((key, neighbors))
+        .map(|line| {
+            let (key, rest) = line.split_once(':').unwrap();
+            (key, rest.split_ascii_whitespace().collect())
         })
         .collect();
-
-    (solve_a(&graph).to_string(), "".to_string())
+    (
+        count_paths(&graph, "you", "out", &[]).to_string(),
+        count_paths(&graph, "svr", "out", &["dac", "fft"]).to_string(),
+    )
 }
@@ -14,92 +14,657 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use std::collections::HashSet;
+
+use crate::common::nonempty_lines;
+use crate::common::parse_number;
+use crate::common::ParseError;
 use crate::common::Solution;
+use crate::search::dlx::ExactCover;
+use crate::util::shape::Shape;
 
-struct Present {
-    w: usize,
-    h: usize,
-    pixels: usize,
-}
+/// Bounds on the sides of a region and the number of each present, so that the areas and their
+/// sums fit in a `usize`.
+const MAX_SIDE: usize = 1000;
+const MAX_COUNT: usize = 1_000_000;
 
-struct Region {
+pub struct Region {
     dim: (usize, usize),
     num_presents: Vec<usize>,
 }
 
-fn solve_a(presents: &[Present], regions: &[Region]) -> usize {
-    regions
+/// Whether a region fits its presents, as far as the area bounds can tell.
+enum Bound {
+    /// Every present fits in a slot the size of the largest bounding box.
+    FitsInSlots {
+        presents: usize,
+        slots: usize,
+    },
+    /// The presents cover more tiles than the region has.
+    TooSmall {
+        tiles: usize,
+        space: usize,
+    },
+    Inconclusive,
+}
+
+fn bound(
+    presents: &[Shape],
+    Region {
+        dim: (dimx, dimy),
+        num_presents,
+    }: &Region,
+) -> Bound {
+    let space = dimx * dimy;
+    let (bboxw, bboxh) = num_presents
         .iter()
-        .filter(
-            |Region {
-                 dim: (dimx, dimy),
-                 num_presents,
-             }| {
-                let space = dimx * dimy;
-                let (bboxw, bboxh) = num_presents
-                    .iter()
-                    .zip(presents)
-                    .map(|(n, p)| if *n > 0 { (p.w, p.h) } else { (0, 0) })
-                    .fold((0, 0), |(maxw, maxh), (w, h)| {
-                        (std::cmp::max(maxw, w), std::cmp::max(maxh, h))
-                    });
-                let space_bboxed = (dimx / bboxw) * (dimy / bboxh);
-
-                if num_presents.iter().copied().sum::<usize>() <= space_bboxed {
-                    true
-                } else {
-                    let presents_size: usize = num_presents
-                        .iter()
-                        .enumerate()
-                        .map(|(i, n)| n * presents[i].pixels)
-                        .sum();
-                    if presents_size <= space {
-                        todo!()
-                    } else {
-                        false
+        .zip(presents)
+        .map(|(n, p)| if *n > 0 { p.size() } else { (0, 0) })
+        .fold((1, 1), |(maxw, maxh), (w, h)| {
+            (std::cmp::max(maxw, w), std::cmp::max(maxh, h))
+        });
+    let space_bboxed = (dimx / bboxw) * (dimy / bboxh);
+    let total_presents = num_presents.iter().copied().sum::<usize>();
+
+    if total_presents <= space_bboxed {
+        Bound::FitsInSlots {
+            presents: total_presents,
+            slots: space_bboxed,
+        }
+    } else {
+        let presents_size: usize = num_presents
+            .iter()
+            .enumerate()
+            .map(|(i, n)| n * presents[i].area())
+            .sum();
+        if presents_size <= space {
+            Bound::Inconclusive
+        } else {
+            Bound::TooSmall {
+                tiles: presents_size,
+                space,
+            }
+        }
+    }
+}
+
+/// The tiles covered by each placement of any orientation of `present` in the region, numbered in
+/// row-major order and listed in increasing order.
+fn placements(present: &Shape, (dimx, dimy): (usize, usize)) -> Vec<Vec<usize>> {
+    let mut result = Vec::new();
+    for orientation in present.orientations() {
+        let (w, h) = orientation.size();
+        if w > dimx || h > dimy {
+            continue;
+        }
+        for y in 0..=dimy - h {
+            for x in 0..=dimx - w {
+                result.push(
+                    orientation
+                        .cells()
+                        .map(|(r, c)| (y + r) * dimx + x + c)
+                        .collect(),
+                );
+            }
+        }
+    }
+    result
+}
+
+/// The most search states [Packing] remembers as failed. The memo is cleared when it is full, so
+/// that large searches run slower instead of running out of memory.
+const MAX_FAILED: usize = 1 << 20;
+
+/// A region being packed, with one bit per tile in row-major order that is set once the tile is
+/// covered by a present or given up as empty.
+///
+/// The search always covers the first empty tile, so every tile before it is filled, and every
+/// tile `reach` or more after it is still empty since no placement spans that far. The state of
+/// the search is therefore the first empty tile, the bits of the `reach` tiles from it, and the
+/// number of each present left to pack.
+struct Packing {
+    dimx: usize,
+    filled: Vec<u64>,
+    /// For each present and tile, the tiles covered by each placement of the present whose first
+    /// tile in row-major order is that tile.
+    placements: Vec<Vec<Vec<Vec<usize>>>>,
+    areas: Vec<usize>,
+    /// How many tiles from its first tile a placement can cover.
+    reach: usize,
+    /// States already known not to lead to a packing, as the first empty tile, the number of each
+    /// present left and the bits of the tiles in reach.
+    failed: HashSet<Vec<u64>>,
+    /// The flood fill in [Packing::dead_tiles_around] marks each tile with the number of the fill that
+    /// reached it.
+    fills: Vec<usize>,
+    fill: usize,
+}
+
+impl Packing {
+    fn new(presents: &[Shape], (dimx, dimy): (usize, usize)) -> Self {
+        let placements: Vec<Vec<Vec<Vec<usize>>>> = presents
+            .iter()
+            .map(|present| {
+                let mut by_tile = vec![Vec::new(); dimx * dimy];
+                for tiles in placements(present, (dimx, dimy)) {
+                    by_tile[tiles[0]].push(tiles);
+                }
+                by_tile
+            })
+            .collect();
+        let reach = placements
+            .iter()
+            .flatten()
+            .flatten()
+            .map(|tiles| tiles[tiles.len() - 1] - tiles[0] + 1)
+            .max()
+            .unwrap_or(1);
+        Self {
+            dimx,
+            filled: vec![0; (dimx * dimy).div_ceil(64)],
+            placements,
+            areas: presents.iter().map(Shape::area).collect(),
+            reach,
+            failed: HashSet::new(),
+            fills: vec![0; dimx * dimy],
+            fill: 0,
+        }
+    }
+
+    fn num_tiles(&self) -> usize {
+        self.fills.len()
+    }
+
+    fn is_filled(&self, tile: usize) -> bool {
+        self.filled[tile / 64] & (1 << (tile % 64)) != 0
+    }
+
+    fn toggle(&mut self, tile: usize) {
+        self.filled[tile / 64] ^= 1 << (tile % 64);
+    }
+
+    /// The bits of the `len` tiles from `from`, for `len` at most 64.
+    fn bits(&self, from: usize, len: usize) -> u64 {
+        let (word, offset) = (from / 64, from % 64);
+        let mut bits = self.filled[word] >> offset;
+        if offset > 0 && word + 1 < self.filled.len() {
+            bits |= self.filled[word + 1] << (64 - offset);
+        }
+        if len < 64 {
+            bits &= (1 << len) - 1;
+        }
+        bits
+    }
+
+    /// The state of the search when `tile` is the first empty tile.
+    fn state(&self, tile: usize, remaining: &[usize]) -> Vec<u64> {
+        let end = (tile + self.reach).min(self.num_tiles());
+        // Tiles and counts are bounded in parse, so they fit in a u64.
+        std::iter::once(tile)
+            .chain(remaining.iter().copied())
+            .map(|n| u64::try_from(n).unwrap())
+            .chain(
+                (tile..end)
+                    .step_by(64)
+                    .map(|t| self.bits(t, (end - t).min(64))),
+            )
+            .collect()
+    }
+
+    /// The tiles next to `tile` in the region.
+    fn neighbors(&self, tile: usize) -> impl Iterator<Item = usize> {
+        let dimx = self.dimx;
+        let num_tiles = self.num_tiles();
+        [
+            tile.checked_sub(dimx),
+            Some(tile + dimx).filter(|t| *t < num_tiles),
+            Some(tile).filter(|t| t % dimx > 0).map(|t| t - 1),
+            Some(tile + 1).filter(|t| t % dimx > 0),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// The number of empty tiles next to `tiles` that no present can cover, because they are in a
+    /// pocket smaller than `min_area` closed off by filled tiles and the edges of the region.
+    fn dead_tiles_around(&mut self, tiles: &[usize], min_area: usize) -> usize {
+        // Fills numbered above `first` happened in this call. A fill that stopped early found a
+        // pocket that is too large, so any later fill that reaches its tiles is in it too.
+        let first = self.fill;
+        let mut dead = 0;
+        let mut stack = Vec::new();
+        for start in tiles
+            .iter()
+            .flat_map(|t| self.neighbors(*t))
+            .collect::<Vec<_>>()
+        {
+            if self.is_filled(start) || self.fills[start] > first {
+                continue;
+            }
+            self.fill += 1;
+            self.fills[start] = self.fill;
+            stack.clear();
+            stack.push(start);
+            let mut size = 0;
+            let mut closed = true;
+            'fill: while let Some(t) = stack.pop() {
+                size += 1;
+                if size >= min_area {
+                    closed = false;
+                    break;
+                }
+                for next in self.neighbors(t) {
+                    if self.is_filled(next) || self.fills[next] == self.fill {
+                        continue;
+                    } else if self.fills[next] > first {
+                        closed = false;
+                        break 'fill;
                     }
+                    self.fills[next] = self.fill;
+                    stack.push(next);
                 }
-            },
-        )
+            }
+            if closed {
+                dead += size;
+            }
+        }
+        dead
+    }
+
+    /// Cover the first empty tile from `tile` on, either with the first tile of a remaining
+    /// present or by leaving it empty if `waste` allows, and recurse. `waste` is the number of
+    /// empty tiles beyond the area of the remaining presents, so the presents always have room
+    /// by area, and a move is skipped as soon as it closes off more tiles than `waste` allows to
+    /// stay empty.
+    fn pack(&mut self, tile: usize, remaining: &mut [usize], waste: usize) -> bool {
+        let Some(min_area) = (0..remaining.len())
+            .filter(|i| remaining[*i] > 0)
+            .map(|i| self.areas[i])
+            .min()
+        else {
+            return true;
+        };
+        let Some(tile) = (tile..self.num_tiles()).find(|t| !self.is_filled(*t)) else {
+            return false;
+        };
+        let state = self.state(tile, remaining);
+        if self.failed.contains(&state) {
+            return false;
+        }
+
+        for i in 0..remaining.len() {
+            if remaining[i] > 0 {
+                for p in 0..self.placements[i][tile].len() {
+                    let tiles = std::mem::take(&mut self.placements[i][tile][p]);
+                    let fits = tiles.iter().all(|t| !self.is_filled(*t));
+                    let mut packed = false;
+                    if fits {
+                        tiles.iter().for_each(|t| self.toggle(*t));
+                        if self.dead_tiles_around(&tiles, min_area) <= waste {
+                            remaining[i] -= 1;
+                            packed = self.pack(tile + 1, remaining, waste);
+                            remaining[i] += 1;
+                        }
+                        tiles.iter().for_each(|t| self.toggle(*t));
+                    }
+                    self.placements[i][tile][p] = tiles;
+                    if packed {
+                        return true;
+                    }
+                }
+            }
+        }
+
+        if waste > 0 {
+            self.toggle(tile);
+            let packed = self.dead_tiles_around(&[tile], min_area) < waste
+                && self.pack(tile + 1, remaining, waste - 1);
+            self.toggle(tile);
+            if packed {
+                return true;
+            }
+        }
+        if self.failed.len() >= MAX_FAILED {
+            self.failed.clear();
+        }
+        self.failed.insert(state);
+        false
+    }
+}
+
+/// Whether the presents can be packed into the region, by search if the area bounds do not tell.
+fn fits_by_search(presents: &[Shape], region: &Region) -> bool {
+    let (dimx, dimy) = region.dim;
+    // The presents may be reflected, so packing the transposed region is equivalent, and filling
+    // the tiles along the shorter side first keeps the frontier of the search short.
+    let dim = (dimx.min(dimy), dimx.max(dimy));
+    let area: usize = region
+        .num_presents
+        .iter()
+        .zip(presents)
+        .map(|(n, p)| n * p.area())
+        .sum();
+    Packing::new(presents, dim).pack(0, &mut region.num_presents.clone(), dimx * dimy - area)
+}
+
+/// Whether the presents can be packed into the region, as an exact cover problem: each present
+/// must be placed once, and each tile may be covered at most once.
+///
+/// Each copy of a present is its own column, so the search tries every order of identical copies,
+/// and regions that do not fit take time factorial in the number of copies. This is only
+/// practical for regions as small as those in the example, as a cross-check of [fits_by_search].
+fn fits_by_exact_cover(presents: &[Shape], region: &Region) -> bool {
+    let (dimx, dimy) = region.dim;
+    let total: usize = region.num_presents.iter().sum();
+    let mut problem = ExactCover::new(total, dimx * dimy);
+    let mut copy = 0;
+    for (present, n) in presents.iter().zip(&region.num_presents) {
+        let placements = placements(present, region.dim);
+        for _ in 0..*n {
+            for tiles in &placements {
+                let columns: Vec<usize> = std::iter::once(copy)
+                    .chain(tiles.iter().map(|t| total + t))
+                    .collect();
+                problem.add_row(&columns);
+            }
+            copy += 1;
+        }
+    }
+    problem.first_solution().is_some()
+}
+
+fn solve_a_with(
+    (presents, regions): &(Vec<Shape>, Vec<Region>),
+    fits_by: fn(&[Shape], &Region) -> bool,
+) -> usize {
+    regions
+        .iter()
+        .filter(|region| match bound(presents, region) {
+            Bound::FitsInSlots { .. } => true,
+            Bound::TooSmall { .. } => false,
+            Bound::Inconclusive => fits_by(presents, region),
+        })
         .count()
 }
 
+pub fn solve_a(input: &(Vec<Shape>, Vec<Region>)) -> usize {
+    solve_a_with(input, fits_by_search)
+}
+
+/// The last day has only one puzzle.
+pub fn solve_b(_input: &(Vec<Shape>, Vec<Region>)) -> String {
+    "".to_string()
+}
+
+/// The present shapes, numbered in order, and the regions with their lists of presents.
+pub fn parse(lines: &[String]) -> Result<(Vec<Shape>, Vec<Region>), ParseError> {
+    // The number of rows and the cells of each present so far.
+    let mut presents: Vec<(usize, Vec<(usize, usize)>)> = Vec::new();
+    let mut regions = Vec::new();
+    for (l, line) in nonempty_lines(lines) {
+        let (head, rest) = line.split_once(':').unwrap_or((line, ""));
+        if let Some((dimx, dimy)) = head.split_once('x') {
+            let num_presents: Vec<usize> = rest
+                .split_ascii_whitespace()
+                .map(|n| parse_number(l, n))
+                .collect::<Result<_, _>>()?;
+            if num_presents.len() != presents.len() {
+                return Err(ParseError::new(
+                    l,
+                    format!("Expected {} present counts", presents.len()),
+                ));
+            } else if num_presents.iter().any(|n| *n > MAX_COUNT) {
+                return Err(ParseError::new(
+                    l,
+                    format!("Expected at most {MAX_COUNT} of each present"),
+                ));
+            }
+            let dim = (parse_number(l, dimx)?, parse_number(l, dimy)?);
+            if dim.0 > MAX_SIDE || dim.1 > MAX_SIDE {
+                return Err(ParseError::new(
+                    l,
+                    format!("Expected regions within {MAX_SIDE} by {MAX_SIDE}"),
+                ));
+            }
+            regions.push(Region { dim, num_presents });
+        } else if line.ends_with(':') {
+            if !regions.is_empty() || parse_number::<usize>(l, head)? != presents.len() {
+                return Err(ParseError::new(l, format!("Unexpected present: {head:?}")));
+            }
+            presents.push((0, Vec::new()));
+        } else {
+            if let Some(ch) = line.chars().find(|ch| *ch != '#' && *ch != '.') {
+                return Err(ParseError::new(l, format!("Invalid pixel: {ch:?}")));
+            }
+            let (rows, cells) = presents
+                .last_mut()
+                .filter(|_| regions.is_empty())
+                .ok_or_else(|| ParseError::new(l, "Expected a present number"))?;
+            if *rows >= 64 || line.len() > 64 {
+                return Err(ParseError::new(l, "Expected presents within 64 by 64"));
+            }
+            cells.extend(
+                line.chars()
+                    .enumerate()
+                    .filter(|(_, ch)| *ch == '#')
+                    .map(|(c, _)| (*rows, c)),
+            );
+            *rows += 1;
+        }
+    }
+    let presents: Vec<Shape> = presents
+        .into_iter()
+        .map(|(_, cells)| Shape::from_cells(cells))
+        .collect();
+    if presents.iter().any(Shape::is_empty) {
+        Err(ParseError::input("Expected every present to have a shape"))
+    } else {
+        Ok((presents, regions))
+    }
+}
+
+fn solve_with(lines: &[String], fits_by: fn(&[Shape], &Region) -> bool) -> Solution {
+    let input = parse(lines).unwrap_or_else(|e| panic!("{e}"));
+    (solve_a_with(&input, fits_by).to_string(), solve_b(&input))
+}
+
 pub fn solve(lines: &[String]) -> Solution {
-    let (presents, regions): (Vec<Present>, Vec<Region>) = lines
+    solve_with(lines, fits_by_search)
+}
+
+/// Solve by exact cover, which is only practical for regions as small as those in the example.
+pub fn solve_dlx(lines: &[String]) -> Solution {
+    solve_with(lines, fits_by_exact_cover)
+}
+
+/// Why each region does or does not fit its presents.
+pub fn explain(lines: &[String]) -> Vec<String> {
+    let (presents, regions) = parse(lines).unwrap_or_else(|e| panic!("{e}"));
+    regions
         .iter()
-        .map(|line| line.trim())
-        .filter(|line| !line.is_empty())
-        .fold(
-            (Vec::new(), Vec::new()),
-            |(mut presents, mut regions), line| {
-                if let Some(region) = line.split_once('x').and_then(|(dimx, rest)| {
-                    let (dimy, rest) = rest.split_once(':')?;
-                    Some(Region {
-                        dim: (dimx.parse().ok()?, dimy.parse().ok()?),
-                        num_presents: rest
-                            .trim()
-                            .split(' ')
-                            .map(|ch| ch.parse())
-                            .collect::<Result<_, _>>()
-                            .ok()?,
-                    })
-                }) {
-                    regions.push(region);
-                } else if let Some((_, _)) = line.split_once(':') {
-                    presents.push(Present {
-                        w: 0,
-                        h: 0,
-                        pixels: 0,
-                    });
-                } else {
-                    let p = presents.last_mut().unwrap();
-                    p.w = std::cmp::max(p.w, line.len());
-                    p.h += 1;
-                    p.pixels += line.chars().filter(|ch| *ch == '#').count();
+        .enumerate()
+        .map(|(i, region)| {
+            let (dimx, dimy) = region.dim;
+            let reason = match bound(&presents, region) {
+                Bound::FitsInSlots { presents, slots } => {
+                    format!("fits: {presents} presents in {slots} bounding box slots")
                 }
-                (presents, regions)
-            },
-        );
+                Bound::TooSmall { tiles, space } => {
+                    format!("does not fit: presents cover {tiles} of {space} tiles")
+                }
+                Bound::Inconclusive if fits_by_search(&presents, region) => {
+                    "fits: packed by search".to_string()
+                }
+                Bound::Inconclusive => "does not fit: no packing found by search".to_string(),
+            };
+            format!("Region {} ({dimx}x{dimy}): {reason}", i + 1)
+        })
+        .collect()
+}
+
+/// All distinct rotations and reflections of `cells`, each moved to touch the top and left edges
+/// and sorted in row-major order.
+fn orientations(cells: &[(usize, usize)]) -> Vec<Vec<(usize, usize)>> {
+    let mut result: Vec<Vec<(usize, usize)>> = Vec::new();
+    let mut current: Vec<(i64, i64)> = cells
+        .iter()
+        .map(|(r, c)| (i64::try_from(*r).unwrap(), i64::try_from(*c).unwrap()))
+        .collect();
+    for _ in 0..2 {
+        for _ in 0..4 {
+            current = current.into_iter().map(|(r, c)| (c, -r)).collect();
+            let min_r = current.iter().map(|(r, _)| *r).min().unwrap();
+            let min_c = current.iter().map(|(_, c)| *c).min().unwrap();
+            let mut normalised: Vec<(usize, usize)> = current
+                .iter()
+                .map(|(r, c)| {
+                    (
+                        usize::try_from(r - min_r).unwrap(),
+                        usize::try_from(c - min_c).unwrap(),
+                    )
+                })
+                .collect();
+            normalised.sort();
+            if !result.contains(&normalised) {
+                result.push(normalised);
+            }
+        }
+        current = current.into_iter().map(|(r, c)| (r, -c)).collect();
+    }
+    result
+}
+
+/// Fill the first empty cell of `grid`, either with the top-left cell of a remaining present or
+/// by leaving it empty if there is space to waste, and recurse.
+fn pack(
+    grid: &mut [Vec<bool>],
+    shapes: &[Vec<Vec<(usize, usize)>>],
+    remaining: &mut [usize],
+    waste: usize,
+) -> bool {
+    if remaining.iter().all(|n| *n == 0) {
+        return true;
+    }
+    let Some((r, c)) = (0..grid.len())
+        .flat_map(|r| (0..grid[r].len()).map(move |c| (r, c)))
+        .find(|(r, c)| !grid[*r][*c])
+    else {
+        return false;
+    };
+
+    for i in 0..shapes.len() {
+        if remaining[i] > 0 {
+            for cells in &shapes[i] {
+                let (_, c0) = cells[0];
+                if c >= c0 {
+                    let placed: Vec<(usize, usize)> =
+                        cells.iter().map(|(dr, dc)| (r + dr, c - c0 + dc)).collect();
+                    if placed
+                        .iter()
+                        .all(|(rr, cc)| grid.get(*rr).and_then(|row| row.get(*cc)) == Some(&false))
+                    {
+                        for (rr, cc) in &placed {
+                            grid[*rr][*cc] = true;
+                        }
+                        remaining[i] -= 1;
+                        let packed = pack(grid, shapes, remaining, waste);
+                        remaining[i] += 1;
+                        for (rr, cc) in &placed {
+                            grid[*rr][*cc] = false;
+                        }
+                        if packed {
+                            return true;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if waste > 0 {
+        grid[r][c] = true;
+        let packed = pack(grid, shapes, remaining, waste - 1);
+        grid[r][c] = false;
+        packed
+    } else {
+        false
+    }
+}
+
+/// Reference implementation: accept regions with room for every present's bounding box side by
+/// side, reject regions with less space than the presents' total area, and search exhaustively
+/// for a packing of the rest.
+pub fn solve_reference(lines: &[String]) -> Solution {
+    let mut shapes: Vec<Vec<(usize, usize)>> = Vec::new();
+    let mut regions: Vec<((usize, usize), Vec<usize>)> = Vec::new();
+    for line in lines
+        .iter()
+        .map(|line| line.trim())
+        .filter(|l| !l.is_empty())
+    {
+        let (head, rest) = line.split_once(':').unwrap_or((line, ""));
+        if let Some((w, h)) = head.split_once('x') {
+            regions.push((
+                (w.parse().unwrap(), h.parse().unwrap()),
+                rest.split_ascii_whitespace()
+                    .map(|n| n.parse().unwrap())
+                    .collect(),
+            ));
+        } else if line.ends_with(':') {
+            shapes.push(Vec::new());
+        } else {
+            let shape = shapes.last_mut().unwrap();
+            let r = shape.iter().map(|(r, _)| r + 1).max().unwrap_or(0);
+            shape.extend(
+                line.chars()
+                    .enumerate()
+                    .filter(|(_, ch)| *ch == '#')
+                    .map(|(c, _)| (r, c)),
+            );
+        }
+    }
+    let shapes: Vec<Vec<Vec<(usize, usize)>>> =
+        shapes.iter().map(|cells| orientations(cells)).collect();
+
+    let sol_a = regions
+        .into_iter()
+        .filter(|((w, h), counts)| {
+            assert_eq!(
+                counts.len(),
+                shapes.len(),
+                "Expected a count for every present"
+            );
+            let area: usize = counts
+                .iter()
+                .zip(&shapes)
+                .map(|(n, orientations)| n * orientations[0].len())
+                .sum();
+            let (bbox_w, bbox_h) = counts
+                .iter()
+                .zip(&shapes)
+                .filter(|(n, _)| **n > 0)
+                .map(|(_, orientations)| {
+                    let cells = &orientations[0];
+                    (
+                        cells.iter().map(|(_, c)| c + 1).max().unwrap(),
+                        cells.iter().map(|(r, _)| r + 1).max().unwrap(),
+                    )
+                })
+                .fold((1, 1), |(mw, mh), (bw, bh)| (mw.max(bw), mh.max(bh)));
+            if area > w * h {
+                false
+            } else if counts.iter().sum::<usize>() <= (w / bbox_w) * (h / bbox_h) {
+                true
+            } else {
+                pack(
+                    &mut vec![vec![false; *w]; *h],
+                    &shapes,
+                    &mut counts.clone(),
+                    w * h - area,
+                )
+            }
+        })
+        .count();
 
-    (solve_a(&presents, &regions).to_string(), "".to_string())
+    (sol_a.to_string(), "".to_string())
 }
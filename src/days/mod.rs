@@ -27,19 +27,178 @@ pub mod day10;
 pub mod day11;
 pub mod day12;
 
+use crate::common::AocError;
+use crate::common::ParseError;
+use crate::common::Solution;
+
+pub type Solver = fn(&[String]) -> Solution;
+
+/// A day's parse step, with the parsed input discarded.
+pub type Parser = fn(&[String]) -> Result<(), ParseError>;
+
+/// Runs a day's phases on an input one at a time: calls `run(name, phase)` for `"parse"`,
+/// `"part A"` and `"part B"`, where `phase` performs only that step. The parts operate on an
+/// input parsed in advance.
+pub type Phases = fn(&[String], &mut dyn FnMut(&'static str, &mut dyn FnMut()));
+
+/// Describes the witness behind a day's answers, one line at a time.
+pub type Explainer = fn(&[String]) -> Vec<String>;
+
+/// Draws a picture of a day's final state for its input.
+pub type Renderer = fn(&[String]) -> crate::render::Drawing;
+
+/// Name of the variant registered as each day's `solve` function.
+pub const DEFAULT_VARIANT: &str = "default";
+
 macro_rules! days {
-    ($($day_mod:ident),*) => {
-        pub fn get_solver(day: u8) -> Option<fn(&[String]) -> crate::common::Solution> {
+    ($($day_mod:ident $({ $($variant:ident: $variant_fn:ident),* $(,)? })?),* $(,)?) => {
+        pub fn get_solver(day: u8) -> Option<Solver> {
             match format!("day{:02}", day).as_str() {
                 $(stringify!($day_mod) => Some($day_mod::solve),)*
                     _ => None,
             }
         }
+
+        pub fn get_parser(day: u8) -> Option<Parser> {
+            match format!("day{:02}", day).as_str() {
+                $(stringify!($day_mod) => Some(|lines| $day_mod::parse(lines).map(|_| ())),)*
+                    _ => None,
+            }
+        }
+
+        /// Parse the input for `day` once and solve both parts of it with the default variant.
+        fn solve_parsed(day: u8, lines: &[String]) -> Option<Result<Solution, ParseError>> {
+            match format!("day{:02}", day).as_str() {
+                $(stringify!($day_mod) => Some($day_mod::parse(lines).map(|input| {
+                    (
+                        $day_mod::solve_a(&input).to_string(),
+                        $day_mod::solve_b(&input).to_string(),
+                    )
+                })),)*
+                    _ => None,
+            }
+        }
+
+        pub fn get_explainer(day: u8) -> Option<Explainer> {
+            match format!("day{:02}", day).as_str() {
+                $(stringify!($day_mod) => Some($day_mod::explain),)*
+                    _ => None,
+            }
+        }
+
+        pub fn get_phases(day: u8) -> Option<Phases> {
+            match format!("day{:02}", day).as_str() {
+                $(stringify!($day_mod) => Some(|lines, run| {
+                    run("parse", &mut || {
+                        let _ = std::hint::black_box($day_mod::parse(lines));
+                    });
+                    let input = $day_mod::parse(lines).unwrap_or_else(|e| panic!("{e}"));
+                    run("part A", &mut || {
+                        std::hint::black_box($day_mod::solve_a(&input));
+                    });
+                    run("part B", &mut || {
+                        std::hint::black_box($day_mod::solve_b(&input));
+                    });
+                }),)*
+                    _ => None,
+            }
+        }
+
+        /// All solver variants for `day`, starting with [`DEFAULT_VARIANT`].
+        pub fn get_variants(day: u8) -> Option<Vec<(&'static str, Solver)>> {
+            match format!("day{:02}", day).as_str() {
+                $(stringify!($day_mod) => Some(vec![
+                    (DEFAULT_VARIANT, $day_mod::solve as Solver),
+                    $($((stringify!($variant), $day_mod::$variant_fn as Solver),)*)?
+                ]),)*
+                    _ => None,
+            }
+        }
     };
 }
 
+pub fn get_variant(day: u8, variant: &str) -> Option<Solver> {
+    get_variants(day)?
+        .into_iter()
+        .find(|(name, _)| *name == variant)
+        .map(|(_, solve)| solve)
+}
+
+/// The days that can draw their final state.
+pub fn get_renderer(day: u8) -> Option<Renderer> {
+    match day {
+        4 => Some(day04::render),
+        8 => Some(day08::render),
+        9 => Some(day09::render),
+        _ => None,
+    }
+}
+
+/// Parse a day number given by the user, and check that the day has a solver.
+pub fn parse_day(day: &str) -> Result<u8, AocError> {
+    day.parse()
+        .ok()
+        .filter(|day| get_solver(*day).is_some())
+        .ok_or_else(|| AocError::UnknownDay(day.to_string()))
+}
+
+/// Run the named solver variant of `day`, reporting malformed input as an error instead of a
+/// panic. The default variant solves the input it parsed; the others take the raw lines, so for
+/// them the input is parsed twice: once to check it and once by the solver.
+pub fn try_solve(day: u8, variant: &str, lines: &[String]) -> Result<Solution, AocError> {
+    if variant == DEFAULT_VARIANT {
+        return Ok(solve_parsed(day, lines).ok_or_else(|| AocError::UnknownDay(day.to_string()))??);
+    }
+    let parse = get_parser(day).ok_or_else(|| AocError::UnknownDay(day.to_string()))?;
+    let solve = get_variant(day, variant).ok_or_else(|| AocError::UnknownVariant {
+        day,
+        variant: variant.to_string(),
+    })?;
+    parse(lines)?;
+    Ok(solve(lines))
+}
+
 pub fn all_numbers() -> Vec<u8> {
     (1..=25).filter(|&day| get_solver(day).is_some()).collect()
 }
 
-days!(day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12);
+days!(
+    day01 {
+        reference: solve_reference
+    },
+    day02 {
+        reference: solve_reference
+    },
+    day03 {
+        reference: solve_reference
+    },
+    day04 {
+        reference: solve_reference
+    },
+    day05 {
+        reference: solve_reference
+    },
+    day06 {
+        reference: solve_reference
+    },
+    day07 {
+        reference: solve_reference
+    },
+    day08 {
+        reference: solve_reference
+    },
+    day09 {
+        reference: solve_reference
+    },
+    day10 {
+        bfs: solve_bfs,
+        reference: solve_reference
+    },
+    day11 {
+        reference: solve_reference
+    },
+    day12 {
+        dlx: solve_dlx,
+        reference: solve_reference
+    },
+);
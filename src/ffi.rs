@@ -0,0 +1,123 @@
+// Solutions to Advent of Code 2025
+// Copyright (C) 2025  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! C API over the [`days`](crate::days) registry. The header `ffi/adventofcode_2025.h` is
+//! generated from this file alone; building with the `ffi` feature checks that it is up to date.
+
+use std::ffi::c_char;
+use std::ffi::CString;
+use std::panic::catch_unwind;
+
+use crate::common::AocError;
+use crate::days;
+
+/// Result codes returned by the `aoc_*` functions.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AocStatus {
+    Ok = 0,
+    UnknownDay = 1,
+    InvalidInput = 2,
+    Panic = 3,
+}
+
+/// Solve `day` for the `len` bytes of UTF-8 input at `input`.
+///
+/// On success, `*out_a` and `*out_b` are set to NUL-terminated answers which the caller must
+/// release with [`aoc_free_string`]. On failure they are set to `NULL`, and the status is
+/// `InvalidInput` if the input is not UTF-8 or does not parse, and `Panic` only if the solver
+/// panics on input that parses.
+///
+/// # Safety
+///
+/// `input` must point to `len` readable bytes, and `out_a` and `out_b` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u8,
+    input: *const u8,
+    len: usize,
+    out_a: *mut *mut c_char,
+    out_b: *mut *mut c_char,
+) -> AocStatus {
+    if out_a.is_null() || out_b.is_null() {
+        return AocStatus::InvalidInput;
+    }
+    *out_a = std::ptr::null_mut();
+    *out_b = std::ptr::null_mut();
+
+    if input.is_null() && len > 0 {
+        return AocStatus::InvalidInput;
+    }
+    let input: &[u8] = if len == 0 {
+        &[]
+    } else {
+        std::slice::from_raw_parts(input, len)
+    };
+    let Ok(input) = std::str::from_utf8(input) else {
+        return AocStatus::InvalidInput;
+    };
+    let lines: Vec<String> = input.lines().map(&str::to_string).collect();
+
+    match catch_unwind(|| days::try_solve(day, days::DEFAULT_VARIANT, &lines)) {
+        Ok(Ok((a, b))) => match (CString::new(a), CString::new(b)) {
+            (Ok(a), Ok(b)) => {
+                *out_a = a.into_raw();
+                *out_b = b.into_raw();
+                AocStatus::Ok
+            }
+            _ => AocStatus::Panic,
+        },
+        Ok(Err(AocError::Parse(_))) => AocStatus::InvalidInput,
+        // The default variant exists for every day, so any other error is for an unknown day.
+        Ok(Err(_)) => AocStatus::UnknownDay,
+        Err(_) => AocStatus::Panic,
+    }
+}
+
+/// Release a string returned by [`aoc_solve`]. Passing `NULL` is a no-op.
+///
+/// # Safety
+///
+/// `s` must be `NULL` or a pointer returned by [`aoc_solve`] that has not already been freed.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Write up to `cap` registered day numbers to `out` and return the total number of registered
+/// days. Call with `cap == 0` to query the required capacity.
+///
+/// # Safety
+///
+/// `out` must be valid for `cap` writes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_days(out: *mut u8, cap: usize) -> usize {
+    let all = days::all_numbers();
+    if !out.is_null() {
+        for (i, day) in all.iter().take(cap).enumerate() {
+            *out.add(i) = *day;
+        }
+    }
+    all.len()
+}
+
+/// The crate version as a static NUL-terminated string.
+#[no_mangle]
+pub extern "C" fn aoc_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
@@ -0,0 +1,42 @@
+// Solutions to Advent of Code 2025
+// Copyright (C) 2025  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::Rng;
+
+/// Between `size / 2` and `size` rotations of 0 to 300 clicks each. A fifth of them stop the dial
+/// at 0, so that the rotations after them start there, and a tenth of them do not turn the dial.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let mut pos = 50;
+    (0..rng.count(size))
+        .map(|_| {
+            let right = rng.chance(50);
+            let clicks = if rng.chance(20) {
+                let to_zero = if right { (100 - pos) % 100 } else { pos };
+                to_zero + 100 * rng.range(0, 2)
+            } else if rng.chance(10) {
+                0
+            } else {
+                rng.range(1, 300)
+            };
+            pos = if right {
+                (pos + clicks) % 100
+            } else {
+                (pos + 100 - clicks % 100) % 100
+            };
+            format!("{}{clicks}", if right { 'R' } else { 'L' })
+        })
+        .collect()
+}
@@ -0,0 +1,51 @@
+// Solutions to Advent of Code 2025
+// Copyright (C) 2025  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::Rng;
+
+/// The largest ID the solver accepts.
+const MAX_ID: usize = 9_999_999_999;
+
+/// Between `size / 2` and `size` disjoint ID ranges of up to 10 digits, half of them around a repeated ID. Like
+/// the puzzle input, no range spans more than one change in the number of digits.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for _ in 0..rng.count(size) {
+        let digits = u32::try_from(rng.range(1, 10)).unwrap();
+        let center = if rng.chance(50) {
+            let block_len =
+                u32::try_from(rng.range(1, usize::try_from((digits / 2).max(1)).unwrap())).unwrap();
+            let block = rng.range(10_usize.pow(block_len - 1), 10_usize.pow(block_len) - 1);
+            block
+                .to_string()
+                .repeat(usize::try_from(digits / block_len).unwrap())
+                .parse()
+                .unwrap()
+        } else {
+            rng.range(10_usize.pow(digits - 1), 10_usize.pow(digits) - 1)
+        };
+        let l = center.saturating_sub(rng.range(0, 200)).max(1);
+        let r = (center + rng.range(0, 200)).min(MAX_ID);
+        if r.ilog10() <= l.ilog10() + 1 && ranges.iter().all(|(ll, rr)| r < *ll || l > *rr) {
+            ranges.push((l, r));
+        }
+    }
+    vec![ranges
+        .into_iter()
+        .map(|(l, r)| format!("{l}-{r}"))
+        .collect::<Vec<_>>()
+        .join(",")]
+}
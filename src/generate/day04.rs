@@ -0,0 +1,30 @@
+// Solutions to Advent of Code 2025
+// Copyright (C) 2025  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::Rng;
+
+/// A grid of between `size / 2` and `size` cells on each side with a random density of rolls.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let (w, h) = (rng.count(size), rng.count(size));
+    let density = rng.range(20, 90);
+    (0..h)
+        .map(|_| {
+            (0..w)
+                .map(|_| if rng.chance(density) { '@' } else { '.' })
+                .collect()
+        })
+        .collect()
+}
@@ -0,0 +1,33 @@
+// Solutions to Advent of Code 2025
+// Copyright (C) 2025  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::Rng;
+
+/// Between `size / 2` and `size` ranges of up to `size` IDs each, and between `size / 2` and
+/// `size` IDs, all below `11 * size`.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let size = size.max(1);
+    let max = 10 * size;
+    let mut lines: Vec<String> = (0..rng.count(size))
+        .map(|_| {
+            let l = rng.range(1, max);
+            format!("{l}-{}", l + rng.range(0, size))
+        })
+        .collect();
+    lines.push(String::new());
+    lines.extend((0..rng.count(size)).map(|_| rng.range(0, max + size).to_string()));
+    lines
+}
@@ -0,0 +1,52 @@
+// Solutions to Advent of Code 2025
+// Copyright (C) 2025  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::Rng;
+
+/// Between `size / 2` and `size` problems of up to 4 operands with up to 4 digits each. Like the puzzle input,
+/// the operands of each problem are either all left-aligned or all right-aligned, and at least one
+/// of them is as wide as the problem.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let rows = rng.range(1, 4);
+    let mut lines = vec![String::new(); rows + 1];
+    for p in 0..rng.count(size) {
+        let width = rng.range(1, 4);
+        let right_aligned = rng.chance(50);
+        let full_width_row = rng.range(0, rows - 1);
+        for (r, line) in lines[..rows].iter_mut().enumerate() {
+            let digits = if r == full_width_row {
+                width
+            } else {
+                rng.range(1, width)
+            };
+            let number: String = (0..digits).map(|_| rng.digit(1, 9)).collect();
+            if p > 0 {
+                line.push(' ');
+            }
+            if right_aligned {
+                line.push_str(&format!("{number: >width$}"));
+            } else {
+                line.push_str(&format!("{number: <width$}"));
+            }
+        }
+        if p > 0 {
+            lines[rows].push(' ');
+        }
+        let op = if rng.chance(50) { '+' } else { '*' };
+        lines[rows].push_str(&format!("{op: <width$}"));
+    }
+    lines
+}
@@ -0,0 +1,39 @@
+// Solutions to Advent of Code 2025
+// Copyright (C) 2025  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::Rng;
+
+/// A manifold between `size` and `2 * size + 1` wide with up to `size` (at most 30) rows of
+/// splitters. Like the puzzle input, splitters are never next to each other or at the edges.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let width = 2 * rng.count(size) + 1;
+    let mut lines = vec![format!(
+        "{}S{}",
+        ".".repeat(width / 2),
+        ".".repeat(width / 2)
+    )];
+    for _ in 0..rng.range(1, size.clamp(1, 30)) {
+        lines.push(".".repeat(width));
+        let mut row = vec!['.'; width];
+        for c in 1..(width - 1) {
+            if row[c - 1] != '^' && rng.chance(30) {
+                row[c] = '^';
+            }
+        }
+        lines.push(row.into_iter().collect());
+    }
+    lines
+}
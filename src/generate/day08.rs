@@ -0,0 +1,33 @@
+// Solutions to Advent of Code 2025
+// Copyright (C) 2025  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::Rng;
+
+/// Between `46 + size / 2` and `46 + size` junction boxes, so that there are at least 1000 pairs, with
+/// coordinates up to `10 * size`.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let max = 10 * size;
+    (0..46 + rng.count(size))
+        .map(|_| {
+            format!(
+                "{},{},{}",
+                rng.range(0, max),
+                rng.range(0, max),
+                rng.range(0, max)
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,160 @@
+// Solutions to Advent of Code 2025
+// Copyright (C) 2025  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+
+use super::Rng;
+
+/// The offsets of a cell's 8 neighbours, clockwise from the one above.
+const RING: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// Whether adding the empty cell `(x, y)` next to the blob keeps the blob free of holes and of
+/// cells touching only diagonally, so that its outline stays a simple polygon. Since the blob
+/// already is, it is enough that the cell's neighbours in the blob form a single run around it,
+/// and that none of them touches it only diagonally.
+fn can_grow(cells: &[Vec<bool>], x: usize, y: usize) -> bool {
+    let ring: Vec<bool> = RING
+        .iter()
+        .map(|(dx, dy)| cells[y.wrapping_add_signed(*dy)][x.wrapping_add_signed(*dx)])
+        .collect();
+    let runs = (0..8).filter(|i| ring[*i] && !ring[(i + 7) % 8]).count();
+    let pinched = [1, 3, 5, 7]
+        .into_iter()
+        .any(|corner| ring[corner] && !ring[corner - 1] && !ring[(corner + 1) % 8]);
+    runs == 1 && !pinched
+}
+
+/// A blob of cells, and the frontier of empty cells next to it in O(1) random access.
+struct Blob {
+    cells: Vec<Vec<bool>>,
+    frontier: Vec<(usize, usize)>,
+    frontier_index: Vec<Vec<Option<usize>>>,
+}
+
+impl Blob {
+    /// An empty blob in a `dim` by `dim` grid, with an empty border of width 1 around it.
+    fn new(dim: usize) -> Self {
+        Self {
+            cells: vec![vec![false; dim + 2]; dim + 2],
+            frontier: Vec::new(),
+            frontier_index: vec![vec![None; dim + 2]; dim + 2],
+        }
+    }
+
+    /// Add the cell `(x, y)` to the blob, and its empty neighbours to the frontier.
+    fn grow(&mut self, x: usize, y: usize) {
+        self.cells[y][x] = true;
+        if let Some(i) = self.frontier_index[y][x].take() {
+            self.frontier.swap_remove(i);
+            if let Some((mx, my)) = self.frontier.get(i) {
+                self.frontier_index[*my][*mx] = Some(i);
+            }
+        }
+        let dim = self.cells.len() - 2;
+        for (nx, ny) in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+            if (1..=dim).contains(&nx)
+                && (1..=dim).contains(&ny)
+                && !self.cells[ny][nx]
+                && self.frontier_index[ny][nx].is_none()
+            {
+                self.frontier_index[ny][nx] = Some(self.frontier.len());
+                self.frontier.push((nx, ny));
+            }
+        }
+    }
+}
+
+/// The corners of the blob's outline, in clockwise order.
+fn outline(cells: &[Vec<bool>]) -> Vec<(usize, usize)> {
+    let mut next: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+    for y in 1..cells.len() - 1 {
+        for x in 1..cells.len() - 1 {
+            if cells[y][x] {
+                if !cells[y - 1][x] {
+                    next.insert((x, y), (x + 1, y));
+                }
+                if !cells[y][x + 1] {
+                    next.insert((x + 1, y), (x + 1, y + 1));
+                }
+                if !cells[y + 1][x] {
+                    next.insert((x + 1, y + 1), (x, y + 1));
+                }
+                if !cells[y][x - 1] {
+                    next.insert((x, y + 1), (x, y));
+                }
+            }
+        }
+    }
+
+    let start = *next.keys().min().unwrap();
+    let mut outline = vec![start];
+    while next[outline.last().unwrap()] != start {
+        outline.push(next[outline.last().unwrap()]);
+    }
+    (0..outline.len())
+        .filter(|i| {
+            let prev = outline[(i + outline.len() - 1) % outline.len()];
+            let next = outline[(i + 1) % outline.len()];
+            prev.0 != next.0 && prev.1 != next.1
+        })
+        .map(|i| outline[i])
+        .collect()
+}
+
+/// Grow a random blob of cells in a grid of `3 + size / 4` by `3 + size / 4` cells, so that its
+/// outline is a simple rectilinear polygon, then spread its corners out to random tile
+/// coordinates. Like the puzzle input, no two corners are on adjacent rows or columns.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let dim = 3 + size / 4;
+    let mut blob = Blob::new(dim);
+    let y = rng.range(1, dim);
+    let x = rng.range(1, dim);
+    blob.grow(x, y);
+
+    for _ in 0..rng.range(1, dim * dim) {
+        if blob.frontier.is_empty() {
+            break;
+        }
+        let (x, y) = blob.frontier[rng.range(0, blob.frontier.len() - 1)];
+        if can_grow(&blob.cells, x, y) {
+            blob.grow(x, y);
+        }
+    }
+
+    let mut spread = |n: usize| -> Vec<usize> {
+        (0..n)
+            .scan(rng.range(0, 5), |v, _| {
+                *v += rng.range(2, 5);
+                Some(*v)
+            })
+            .collect()
+    };
+    let xs = spread(dim + 2);
+    let ys = spread(dim + 2);
+    outline(&blob.cells)
+        .into_iter()
+        .map(|(x, y)| format!("{},{}", xs[x], ys[y]))
+        .collect()
+}
@@ -0,0 +1,73 @@
+// Solutions to Advent of Code 2025
+// Copyright (C) 2025  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::Rng;
+
+/// Between `size / 2` and `size` machines with up to 10 lights and, like the real input, about as
+/// many buttons as lights (at most `size`). The lights and joltage targets are reached by pressing
+/// each button up to `size` (at most 20) times, so every machine is solvable.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    (0..rng.count(size))
+        .map(|_| {
+            let num_lights = rng.range(1, 10);
+            let buttons: Vec<Vec<usize>> = (0..rng
+                .range(num_lights.saturating_sub(1), num_lights + 2)
+                .clamp(1, size.max(1)))
+                .map(|_| {
+                    let mut button: Vec<usize> =
+                        (0..num_lights).filter(|_| rng.chance(40)).collect();
+                    if button.is_empty() {
+                        button.push(rng.range(0, num_lights - 1));
+                    }
+                    button
+                })
+                .collect();
+            let mut lights = vec![false; num_lights];
+            let mut joltage = vec![0; num_lights];
+            for button in &buttons {
+                let presses = rng.range(0, size.min(20));
+                for i in button {
+                    lights[*i] ^= presses % 2 == 1;
+                    joltage[*i] += presses;
+                }
+            }
+            format!(
+                "[{}] {} {{{}}}",
+                lights
+                    .iter()
+                    .map(|on| if *on { '#' } else { '.' })
+                    .collect::<String>(),
+                buttons
+                    .iter()
+                    .map(|button| format!(
+                        "({})",
+                        button
+                            .iter()
+                            .map(|i| i.to_string())
+                            .collect::<Vec<_>>()
+                            .join(",")
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                joltage
+                    .iter()
+                    .map(|j| j.to_string())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            )
+        })
+        .collect()
+}
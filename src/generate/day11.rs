@@ -0,0 +1,55 @@
+// Solutions to Advent of Code 2025
+// Copyright (C) 2025  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::HashSet;
+
+use super::Rng;
+
+/// A random DAG of up to `size` (at most 10000) devices besides `svr`, `you` and `out`, among them
+/// `dac` and `fft`, each with one or two outputs to devices later in a random topological order
+/// starting with `svr`. Every device has a path to `out`.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let mut names: HashSet<String> = ["dac".to_string(), "fft".to_string()].into();
+    let num_devices = rng.range(2, size.clamp(2, 10000));
+    while names.len() < num_devices {
+        let name: String = (0..3)
+            .map(|_| char::from(b'a' + u8::try_from(rng.range(0, 25)).unwrap()))
+            .collect();
+        if !["you", "svr", "out"].contains(&name.as_str()) {
+            names.insert(name);
+        }
+    }
+    let mut order: Vec<String> = names.into_iter().collect();
+    order.sort();
+    rng.shuffle(&mut order);
+    let you = rng.range(0, order.len() / 2);
+    order.insert(you, "you".to_string());
+    order.insert(0, "svr".to_string());
+    order.push("out".to_string());
+
+    let mut lines: Vec<String> = (0..order.len() - 1)
+        .map(|i| {
+            let mut outputs: Vec<&str> = (0..rng.range(1, 2))
+                .map(|_| order[rng.range(i + 1, order.len() - 1)].as_str())
+                .collect();
+            outputs.sort();
+            outputs.dedup();
+            format!("{}: {}", order[i], outputs.join(" "))
+        })
+        .collect();
+    rng.shuffle(&mut lines);
+    lines
+}
@@ -0,0 +1,75 @@
+// Solutions to Advent of Code 2025
+// Copyright (C) 2025  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::Rng;
+
+/// Up to 4 random connected shapes of 4 to 7 cells within 3 by 3, and up to `size` regions of 3
+/// to 6 cells per side with presents covering 50% to 100% of the region's area.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut areas = Vec::new();
+    let num_shapes = rng.range(1, 4);
+    for i in 0..num_shapes {
+        let mut shape = [[false; 3]; 3];
+        shape[1][1] = true;
+        let mut area = 1;
+        let target_area = rng.range(4, 7);
+        while area < target_area {
+            let (r, c) = (rng.range(0, 2), rng.range(0, 2));
+            let adjacent = [
+                (r + 1, c),
+                (r.wrapping_sub(1), c),
+                (r, c + 1),
+                (r, c.wrapping_sub(1)),
+            ]
+            .into_iter()
+            .any(|(rr, cc)| rr < 3 && cc < 3 && shape[rr][cc]);
+            if !shape[r][c] && adjacent {
+                shape[r][c] = true;
+                area += 1;
+            }
+        }
+        areas.push(area);
+        lines.push(format!("{i}:"));
+        lines.extend(
+            shape
+                .iter()
+                .map(|row| row.iter().map(|b| if *b { '#' } else { '.' }).collect()),
+        );
+        lines.push(String::new());
+    }
+
+    for _ in 0..rng.range(1, size.max(1)) {
+        let (w, h) = (rng.range(3, 6), rng.range(3, 6));
+        let mut counts = vec![0; num_shapes];
+        let mut area = 0;
+        let target_area = w * h * rng.range(50, 100) / 100;
+        while area < target_area {
+            let i = rng.range(0, num_shapes - 1);
+            counts[i] += 1;
+            area += areas[i];
+        }
+        lines.push(format!(
+            "{w}x{h}: {}",
+            counts
+                .iter()
+                .map(|n| n.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        ));
+    }
+    lines
+}
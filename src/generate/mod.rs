@@ -0,0 +1,128 @@
+// Solutions to Advent of Code 2025
+// Copyright (C) 2025  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Random puzzle inputs for stress testing and benchmarking. Every generator takes a seeded
+//! [`Rng`] and a `size` knob that scales the number of lines or items in the input.
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+
+pub type Generator = fn(&mut Rng, usize) -> Vec<String>;
+
+/// SplitMix64. Not cryptographically secure, but fast and reproducible from a single seed.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A uniformly random integer in `lo..=hi`.
+    pub fn range(&mut self, lo: usize, hi: usize) -> usize {
+        lo + usize::try_from(self.next_u64() % u64::try_from(hi - lo + 1).unwrap()).unwrap()
+    }
+
+    /// A random number of items for a generator's `size`: between `size / 2` and `size`, and at
+    /// least 1.
+    pub fn count(&mut self, size: usize) -> usize {
+        self.range(size.div_ceil(2).max(1), size.max(1))
+    }
+
+    /// `true` with probability `percent` / 100.
+    pub fn chance(&mut self, percent: usize) -> bool {
+        self.range(1, 100) <= percent
+    }
+
+    /// A random decimal digit in `lo..=hi`.
+    pub fn digit(&mut self, lo: u32, hi: u32) -> char {
+        let d = self.range(usize::try_from(lo).unwrap(), usize::try_from(hi).unwrap());
+        char::from_digit(u32::try_from(d).unwrap(), 10).unwrap()
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range(0, i));
+        }
+    }
+}
+
+macro_rules! generators {
+    ($($day_mod:ident),*) => {
+        pub fn get_generator(day: u8) -> Option<Generator> {
+            match format!("day{:02}", day).as_str() {
+                $(stringify!($day_mod) => Some($day_mod::generate),)*
+                    _ => None,
+            }
+        }
+    };
+}
+
+/// Generate an input for `day` of the given `size`, reproducibly from `seed`.
+pub fn generate(day: u8, seed: u64, size: usize) -> Option<Vec<String>> {
+    get_generator(day).map(|generate| generate(&mut Rng::new(seed), size))
+}
+
+generators!(day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12);
+
+#[cfg(test)]
+mod tests {
+    use super::generate;
+    use crate::days;
+
+    #[test]
+    fn every_day_has_a_generator() {
+        for day in days::all_numbers() {
+            assert!(generate(day, 0, 10).is_some(), "day {day}");
+        }
+    }
+
+    #[test]
+    fn generators_are_deterministic() {
+        for day in days::all_numbers() {
+            for size in [1, 10, 100] {
+                assert_eq!(generate(day, 7, size), generate(day, 7, size), "day {day}");
+            }
+        }
+    }
+
+    #[test]
+    fn generated_inputs_are_accepted() {
+        for day in days::all_numbers() {
+            let solve = days::get_variant(day, "reference").unwrap();
+            for seed in 0..10 {
+                solve(&generate(day, seed, 5).unwrap());
+            }
+        }
+    }
+}
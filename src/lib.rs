@@ -14,8 +14,14 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+#[cfg(feature = "animate")]
+pub mod animate;
 pub mod common;
 pub mod days;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod generate;
+pub mod render;
 
 #[allow(unused)]
 mod search;
@@ -15,21 +15,32 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 mod crate_info;
+mod serve;
 
 use std::path::Path;
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::sync::mpsc;
+use std::sync::mpsc::RecvTimeoutError;
+use std::time::Duration;
 
 use adventofcode_2025::common::day_input_filename;
 use adventofcode_2025::common::get_file_lines;
+use adventofcode_2025::common::AocError;
+use adventofcode_2025::common::Solution;
 use adventofcode_2025::days;
+use adventofcode_2025::generate;
 use clap::App;
 use clap::Arg;
+use clap::ArgMatches;
+use clap::SubCommand;
 
 use crate::crate_info::crate_author;
 use crate::crate_info::crate_description;
 use crate::crate_info::crate_name;
 use crate::crate_info::crate_version;
 
-fn main() -> Result<(), std::io::Error> {
+fn main() -> ExitCode {
     let cli = App::new(crate_name())
         .version(crate_version())
         .about(crate_description())
@@ -43,39 +54,342 @@ fn main() -> Result<(), std::io::Error> {
             Arg::with_name("input-file")
                 .takes_value(true)
                 .help(r#"Path to a file containing input for the chosen day. Use "-" for standard input; omit to use "./inputs/day<day>.in"."#)
+        )
+        .arg(
+            Arg::with_name("variant")
+                .long("variant")
+                .takes_value(true)
+                .requires("day")
+                .help(r#"Name of the solver variant to run. If omitted, the "default" variant is run."#)
+        )
+        .arg(
+            Arg::with_name("explain")
+                .long("explain")
+                .help("Also print the witness behind each answer, such as the items that make up the sum")
+        )
+        .arg(
+            Arg::with_name("timeout")
+                .long("timeout")
+                .takes_value(true)
+                .value_name("seconds")
+                .help("Give up on a day that takes longer than this")
+        )
+        .arg(
+            Arg::with_name("render")
+                .long("render")
+                .takes_value(true)
+                .value_name("path")
+                .requires("day")
+                .help(r#"Draw the day's final state to this file, as SVG if it ends in ".svg" or as PPM if it ends in ".ppm"."#)
+        )
+        .subcommand(
+            SubCommand::with_name("crosscheck")
+                .about("Run all solver variants on the same input and report disagreements")
+                .arg(
+                    Arg::with_name("day")
+                        .takes_value(true)
+                        .help(r#"Day number (1 - 25) to check. If omitted, all days are checked."#)
+                )
+                .arg(
+                    Arg::with_name("input-file")
+                        .takes_value(true)
+                        .help(r#"Path to a file containing input for the chosen day. Use "-" for standard input; omit to use "./inputs/day<day>.in"."#)
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("generate")
+                .about("Print a random input for a day")
+                .arg(
+                    Arg::with_name("day")
+                        .takes_value(true)
+                        .required(true)
+                        .help(r#"Day number (1 - 25) to generate input for."#)
+                )
+                .arg(
+                    Arg::with_name("seed")
+                        .long("seed")
+                        .takes_value(true)
+                        .default_value("0")
+                        .help("Seed for the random number generator")
+                )
+                .arg(
+                    Arg::with_name("size")
+                        .long("size")
+                        .takes_value(true)
+                        .default_value("10")
+                        .help("Size of the input, roughly the number of lines or items")
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("serve")
+                .about("Serve the solvers over HTTP on localhost")
+                .arg(
+                    Arg::with_name("port")
+                        .long("port")
+                        .takes_value(true)
+                        .default_value("2025")
+                        .help("TCP port to listen on")
+                )
         );
 
-    let matches = cli.get_matches();
+    #[cfg(feature = "animate")]
+    let cli = cli
+        .arg(
+            Arg::with_name("animate")
+                .long("animate")
+                .requires("day")
+                .help("Play an animation of the day's solution before printing the answers"),
+        )
+        .arg(
+            Arg::with_name("frame-delay")
+                .long("frame-delay")
+                .takes_value(true)
+                .default_value("50")
+                .help("Milliseconds to pause after each frame of an animation"),
+        );
+
+    match run(&cli.get_matches()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            ExitCode::from(e.exit_code())
+        }
+    }
+}
 
-    if let Some(day) = matches.value_of("day") {
-        run_day(
-            day.parse::<u8>().expect("Invalid day number: {day}"),
-            matches.value_of("input-file").map(Path::new),
+fn run(matches: &ArgMatches) -> Result<(), AocError> {
+    #[cfg(feature = "animate")]
+    adventofcode_2025::animate::set_frame_delay(Duration::from_millis(parse_arg(
+        matches,
+        "frame-delay",
+    )?));
+
+    if let Some(serve_matches) = matches.subcommand_matches("serve") {
+        Ok(serve::serve(parse_arg(serve_matches, "port")?)?)
+    } else if let Some(generate_matches) = matches.subcommand_matches("generate") {
+        generate(
+            generate_matches.value_of("day").unwrap(),
+            parse_arg(generate_matches, "seed")?,
+            parse_arg(generate_matches, "size")?,
         )
+    } else if let Some(crosscheck_matches) = matches.subcommand_matches("crosscheck") {
+        if let Some(day) = crosscheck_matches.value_of("day") {
+            crosscheck(
+                &[days::parse_day(day)?],
+                crosscheck_matches.value_of("input-file").map(Path::new),
+            )
+        } else {
+            crosscheck(&days::all_numbers(), None)
+        }
     } else {
-        run_all_days()
+        let timeout = match matches.value_of("timeout") {
+            Some(seconds) => Some(
+                Duration::try_from_secs_f64(parse_arg(matches, "timeout")?).map_err(|_| {
+                    AocError::InvalidArgument {
+                        name: "timeout",
+                        value: seconds.to_string(),
+                    }
+                })?,
+            ),
+            None => None,
+        };
+        let options = RunOptions {
+            variant: matches.value_of("variant").unwrap_or(days::DEFAULT_VARIANT),
+            render_path: matches.value_of("render").map(Path::new),
+            explain: matches.is_present("explain"),
+            #[cfg(feature = "animate")]
+            animate: matches.is_present("animate"),
+            timeout,
+        };
+        if let Some(day) = matches.value_of("day") {
+            run_day(
+                days::parse_day(day)?,
+                matches.value_of("input-file").map(Path::new),
+                &options,
+            )
+        } else {
+            run_all_days(&options)
+        }
     }
 }
 
-fn run_day(day: u8, input_path: Option<&Path>) -> Result<(), std::io::Error> {
-    println!();
-    println!("=== Day {day: >2} ===");
+/// Parse the value of the argument `name`, which must be present or have a default.
+fn parse_arg<T: FromStr>(matches: &ArgMatches, name: &'static str) -> Result<T, AocError> {
+    let value = matches.value_of(name).unwrap();
+    value.parse().map_err(|_| AocError::InvalidArgument {
+        name,
+        value: value.to_string(),
+    })
+}
+
+struct RunOptions<'a> {
+    variant: &'a str,
+    render_path: Option<&'a Path>,
+    explain: bool,
+    #[cfg(feature = "animate")]
+    animate: bool,
+    timeout: Option<Duration>,
+}
+
+fn generate(day: &str, seed: u64, size: usize) -> Result<(), AocError> {
+    let lines = generate::generate(days::parse_day(day)?, seed, size)
+        .ok_or_else(|| AocError::UnknownDay(day.to_string()))?;
+    for line in lines {
+        println!("{line}");
+    }
+    Ok(())
+}
 
-    let day_func = days::get_solver(day).expect("Unknown day: {day}");
-    let lines = input_path
+fn read_input(day: u8, input_path: Option<&Path>) -> Result<Vec<String>, AocError> {
+    input_path
         .map(get_file_lines)
-        .unwrap_or_else(|| get_file_lines(&day_input_filename(day)))?;
-    let solution = day_func(&lines);
+        .unwrap_or_else(|| get_file_lines(&day_input_filename(day)))
+}
+
+/// Solve `day`, giving up after `timeout` if one is given.
+fn solve(
+    day: u8,
+    variant: &str,
+    lines: &[String],
+    timeout: Option<Duration>,
+) -> Result<Solution, AocError> {
+    let Some(limit) = timeout else {
+        return days::try_solve(day, variant, lines);
+    };
+    let (tx, rx) = mpsc::channel();
+    let worker = {
+        let variant = variant.to_string();
+        let lines = lines.to_vec();
+        std::thread::spawn(move || {
+            let _ = tx.send(days::try_solve(day, &variant, &lines));
+        })
+    };
+    match rx.recv_timeout(limit) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => Err(AocError::Timeout { day, limit }),
+        Err(RecvTimeoutError::Disconnected) => match worker.join() {
+            Err(panic) => std::panic::resume_unwind(panic),
+            Ok(()) => unreachable!("Solver thread exited without a result"),
+        },
+    }
+}
+
+fn run_day(day: u8, input_path: Option<&Path>, options: &RunOptions) -> Result<(), AocError> {
+    println!();
+    if options.variant == days::DEFAULT_VARIANT {
+        println!("=== Day {day: >2} ===");
+    } else {
+        println!("=== Day {day: >2} ({}) ===", options.variant);
+    }
+
+    let lines = read_input(day, input_path)?;
+    #[cfg(feature = "animate")]
+    if options.animate {
+        animate(day, &lines)?;
+    }
+    let solution = solve(day, options.variant, &lines, options.timeout)?;
 
     println!("A: {}", solution.0);
     println!("B: {}", solution.1);
 
+    if options.explain {
+        let explain =
+            days::get_explainer(day).ok_or_else(|| AocError::UnknownDay(day.to_string()))?;
+        for line in explain(&lines) {
+            println!("  {line}");
+        }
+    }
+
+    if let Some(render_path) = options.render_path {
+        let render = days::get_renderer(day).ok_or(AocError::Unsupported {
+            day,
+            what: "picture to render",
+        })?;
+        render(&lines).save(render_path)?;
+    }
+
     Ok(())
 }
 
-fn run_all_days() -> Result<(), std::io::Error> {
+/// Play the animation of `day` on its parsed input.
+#[cfg(feature = "animate")]
+fn animate(day: u8, lines: &[String]) -> Result<(), AocError> {
+    match day {
+        4 => days::day04::animate(&days::day04::parse(lines)?),
+        7 => days::day07::animate(&days::day07::parse(lines)?),
+        _ => {
+            return Err(AocError::Unsupported {
+                day,
+                what: "animation",
+            })
+        }
+    }
+    Ok(())
+}
+
+fn run_all_days(options: &RunOptions) -> Result<(), AocError> {
     for day in days::all_numbers() {
-        run_day(day, None)?
+        run_day(day, None, options)?
     }
     Ok(())
 }
+
+fn crosscheck(days: &[u8], input_path: Option<&Path>) -> Result<(), AocError> {
+    let mut disagreements = Vec::new();
+    for &day in days {
+        println!();
+        println!("=== Day {day: >2} ===");
+
+        let unknown_day = || AocError::UnknownDay(day.to_string());
+        let variants = days::get_variants(day).ok_or_else(unknown_day)?;
+        let parse = days::get_parser(day).ok_or_else(unknown_day)?;
+        let lines = read_input(day, input_path)?;
+        parse(&lines)?;
+        let solutions: Vec<(&str, Solution)> = variants
+            .into_iter()
+            .map(|(name, solve)| (name, solve(&lines)))
+            .collect();
+
+        for (name, (a, b)) in &solutions {
+            println!("{name: <10} A: {a: <20} B: {b}");
+        }
+
+        // The reference variant is the straightforward one, so its answers are the expected ones.
+        let (expected_name, (expected_a, expected_b)) = solutions
+            .iter()
+            .find(|(name, _)| *name == "reference")
+            .unwrap_or(&solutions[0]);
+        let disagreeing_a = solutions
+            .iter()
+            .map(|(name, (a, _))| (name, a))
+            .find(|(_, a)| *a != expected_a);
+        let disagreeing_b = solutions
+            .iter()
+            .map(|(name, (_, b))| (name, b))
+            .find(|(_, b)| *b != expected_b);
+        for (part, expected, disagreeing) in [
+            ('a', expected_a, disagreeing_a),
+            ('b', expected_b, disagreeing_b),
+        ] {
+            if let Some((name, actual)) = disagreeing {
+                println!(
+                    "Variants disagree on day {day}{part}: \
+                     {name} gives {actual:?}, {expected_name} gives {expected:?}"
+                );
+                disagreements.push(AocError::WrongAnswer {
+                    day,
+                    part,
+                    expected: expected.clone(),
+                    actual: actual.clone(),
+                });
+            }
+        }
+    }
+
+    if disagreements.is_empty() {
+        Ok(())
+    } else {
+        println!("Variants disagree on {} part(s)", disagreements.len());
+        Err(disagreements.swap_remove(0))
+    }
+}
@@ -0,0 +1,415 @@
+// Solutions to Advent of Code 2025
+// Copyright (C) 2025  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Pictures of solver state, written as SVG or as binary PPM images.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+pub type Rgb = (u8, u8, u8);
+
+pub const BLACK: Rgb = (0, 0, 0);
+pub const WHITE: Rgb = (255, 255, 255);
+pub const GRAY: Rgb = (160, 160, 160);
+pub const RED: Rgb = (220, 40, 40);
+pub const GREEN: Rgb = (60, 170, 80);
+pub const BLUE: Rgb = (40, 90, 220);
+
+/// Largest width or height of a rasterised drawing, in pixels.
+const MAX_RASTER_SIZE: i32 = 1024;
+
+/// A colour for each of a sequence of categories, cycling through twelve distinct hues.
+pub fn palette(i: usize) -> Rgb {
+    const COLORS: [Rgb; 12] = [
+        (230, 25, 75),
+        (60, 180, 75),
+        (0, 130, 200),
+        (245, 130, 48),
+        (145, 30, 180),
+        (70, 200, 200),
+        (240, 50, 230),
+        (170, 180, 20),
+        (250, 150, 170),
+        (0, 128, 128),
+        (170, 110, 40),
+        (128, 0, 0),
+    ];
+    COLORS[i % COLORS.len()]
+}
+
+pub enum Shape {
+    Rect {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        fill: Rgb,
+    },
+    Circle {
+        x: f64,
+        y: f64,
+        radius: f64,
+        fill: Rgb,
+    },
+    /// A line one pixel wide, however the drawing is scaled.
+    Line {
+        from: (f64, f64),
+        to: (f64, f64),
+        stroke: Rgb,
+    },
+    /// A closed polygon, filled by the even-odd rule and outlined one pixel wide.
+    Polygon {
+        points: Vec<(f64, f64)>,
+        fill: Rgb,
+        stroke: Rgb,
+    },
+}
+
+/// Shapes on a `width` by `height` canvas, with y pointing down. Later shapes are drawn on top of
+/// earlier ones.
+pub struct Drawing {
+    width: f64,
+    height: f64,
+    background: Rgb,
+    shapes: Vec<Shape>,
+}
+
+impl Drawing {
+    pub fn new(width: f64, height: f64, background: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            background,
+            shapes: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, shape: Shape) {
+        self.shapes.push(shape);
+    }
+
+    /// Write the drawing to `path`, as SVG if the file name ends in `.svg` and as PPM if it ends
+    /// in `.ppm`.
+    pub fn save(&self, path: &Path) -> Result<(), std::io::Error> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("svg") => std::fs::write(path, self.to_svg()),
+            Some("ppm") => std::fs::write(path, self.to_ppm()),
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("Expected a file name ending in .svg or .ppm: {path:?}"),
+            )),
+        }
+    }
+
+    pub fn to_svg(&self) -> String {
+        let color = |(r, g, b): Rgb| format!("#{r:02x}{g:02x}{b:02x}");
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {} {}">"#,
+            self.width, self.height
+        );
+        svg.push('\n');
+        let _ = writeln!(
+            svg,
+            r#"<rect width="100%" height="100%" fill="{}"/>"#,
+            color(self.background)
+        );
+        for shape in &self.shapes {
+            let _ = match shape {
+                Shape::Rect {
+                    x,
+                    y,
+                    width,
+                    height,
+                    fill,
+                } => writeln!(
+                    svg,
+                    r#"<rect x="{x}" y="{y}" width="{width}" height="{height}" fill="{}"/>"#,
+                    color(*fill)
+                ),
+                Shape::Circle { x, y, radius, fill } => writeln!(
+                    svg,
+                    r#"<circle cx="{x}" cy="{y}" r="{radius}" fill="{}"/>"#,
+                    color(*fill)
+                ),
+                Shape::Line {
+                    from: (x1, y1),
+                    to: (x2, y2),
+                    stroke,
+                } => writeln!(
+                    svg,
+                    r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}" stroke="{}" stroke-width="1" vector-effect="non-scaling-stroke"/>"#,
+                    color(*stroke)
+                ),
+                Shape::Polygon {
+                    points,
+                    fill,
+                    stroke,
+                } => writeln!(
+                    svg,
+                    r#"<polygon points="{}" fill="{}" fill-rule="evenodd" stroke="{}" stroke-width="1" vector-effect="non-scaling-stroke"/>"#,
+                    points
+                        .iter()
+                        .map(|(x, y)| format!("{x},{y}"))
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                    color(*fill),
+                    color(*stroke)
+                ),
+            };
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// The drawing as a binary PPM image, scaled so that the larger side is at most
+    /// `MAX_RASTER_SIZE` pixels, and small drawings by a whole number of pixels per unit.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let longest = self.width.max(self.height).max(1.0);
+        let max_size = f64::from(MAX_RASTER_SIZE);
+        let scale = if longest <= max_size {
+            (max_size / longest).floor()
+        } else {
+            max_size / longest
+        };
+        let mut raster = Raster::new(
+            clip((self.width * scale).ceil(), MAX_RASTER_SIZE).max(1),
+            clip((self.height * scale).ceil(), MAX_RASTER_SIZE).max(1),
+            self.background,
+        );
+        let px = |(x, y): (f64, f64)| (x * scale, y * scale);
+        for shape in &self.shapes {
+            match shape {
+                Shape::Rect {
+                    x,
+                    y,
+                    width,
+                    height,
+                    fill,
+                } => raster.fill_rect(px((*x, *y)), px((x + width, y + height)), *fill),
+                Shape::Circle { x, y, radius, fill } => {
+                    raster.fill_circle(px((*x, *y)), (radius * scale).max(1.0), *fill)
+                }
+                Shape::Line { from, to, stroke } => raster.line(px(*from), px(*to), *stroke),
+                Shape::Polygon {
+                    points,
+                    fill,
+                    stroke,
+                } => {
+                    let points: Vec<(f64, f64)> = points.iter().copied().map(px).collect();
+                    raster.fill_polygon(&points, *fill);
+                    for (i, p) in points.iter().enumerate() {
+                        raster.line(*p, points[(i + 1) % points.len()], *stroke);
+                    }
+                }
+            }
+        }
+        raster.to_ppm()
+    }
+}
+
+/// A whole number of pixels `v`, clipped to `-1..=limit` so that coordinates off the canvas stay
+/// just off it. The clamp keeps `v` in range of `i32`, so the conversion only drops the fraction.
+fn clip(v: f64, limit: i32) -> i32 {
+    v.clamp(-1.0, f64::from(limit)) as i32
+}
+
+struct Raster {
+    width: i32,
+    height: i32,
+    pixels: Vec<Rgb>,
+}
+
+impl Raster {
+    fn new(width: i32, height: i32, background: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![background; usize::try_from(width * height).unwrap()],
+        }
+    }
+
+    /// Set the pixel at `(x, y)`, or nothing if it is off the canvas.
+    fn set(&mut self, x: i32, y: i32, color: Rgb) {
+        if (0..self.width).contains(&x) && (0..self.height).contains(&y) {
+            self.pixels[usize::try_from(y * self.width + x).unwrap()] = color;
+        }
+    }
+
+    /// Fill the pixels whose centres are in the rectangle between the corners `from` and `to`,
+    /// and at least one pixel.
+    fn fill_rect(&mut self, (x1, y1): (f64, f64), (x2, y2): (f64, f64), color: Rgb) {
+        let span = |a: f64, b: f64, limit: i32| {
+            let lo = clip(a.min(b).round(), limit);
+            (lo, clip(a.max(b).round(), limit).max(lo + 1))
+        };
+        let (xlo, xhi) = span(x1, x2, self.width);
+        let (ylo, yhi) = span(y1, y2, self.height);
+        for y in ylo..yhi {
+            for x in xlo..xhi {
+                self.set(x, y, color);
+            }
+        }
+    }
+
+    fn fill_circle(&mut self, (cx, cy): (f64, f64), radius: f64, color: Rgb) {
+        for y in clip((cy - radius).floor(), self.height)..=clip((cy + radius).ceil(), self.height)
+        {
+            for x in
+                clip((cx - radius).floor(), self.width)..=clip((cx + radius).ceil(), self.width)
+            {
+                let (dx, dy) = (f64::from(x) + 0.5 - cx, f64::from(y) + 0.5 - cy);
+                if dx * dx + dy * dy <= radius * radius {
+                    self.set(x, y, color);
+                }
+            }
+        }
+    }
+
+    /// Draw the part of the line from `(x1, y1)` to `(x2, y2)` that is on the canvas.
+    fn line(&mut self, (x1, y1): (f64, f64), (x2, y2): (f64, f64), color: Rgb) {
+        let (dx, dy) = (x2 - x1, y2 - y1);
+        let (mut t0, mut t1) = (0.0_f64, 1.0_f64);
+        for (start, delta, limit) in [(x1, dx, self.width), (y1, dy, self.height)] {
+            let limit = f64::from(limit);
+            if delta == 0.0 {
+                if !(0.0..=limit).contains(&start) {
+                    return;
+                }
+            } else {
+                let (a, b) = (-start / delta, (limit - start) / delta);
+                t0 = t0.max(a.min(b));
+                t1 = t1.min(a.max(b));
+            }
+        }
+        if t0 > t1 {
+            return;
+        }
+        let steps = clip(
+            ((t1 - t0) * dx.abs().max(dy.abs())).ceil().max(1.0),
+            self.width + self.height,
+        );
+        for i in 0..=steps {
+            let t = t0 + (t1 - t0) * f64::from(i) / f64::from(steps);
+            self.set(
+                clip((x1 + t * dx).floor(), self.width),
+                clip((y1 + t * dy).floor(), self.height),
+                color,
+            );
+        }
+    }
+
+    /// Fill the pixels whose centres are inside the polygon by the even-odd rule.
+    fn fill_polygon(&mut self, points: &[(f64, f64)], color: Rgb) {
+        for y in 0..self.height {
+            let yc = f64::from(y) + 0.5;
+            let mut crossings: Vec<f64> = (0..points.len())
+                .map(|i| (points[i], points[(i + 1) % points.len()]))
+                .filter(|((_, y1), (_, y2))| (*y1 <= yc) != (*y2 <= yc))
+                .map(|((x1, y1), (x2, y2))| x1 + (yc - y1) / (y2 - y1) * (x2 - x1))
+                .collect();
+            crossings.sort_by(f64::total_cmp);
+            for pair in crossings.chunks_exact(2) {
+                let from = clip((pair[0] - 0.5).ceil(), self.width);
+                let to = clip((pair[1] - 0.5).floor(), self.width);
+                for x in from..=to {
+                    self.set(x, y, color);
+                }
+            }
+        }
+    }
+
+    fn to_ppm(&self) -> Vec<u8> {
+        let mut ppm = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        ppm.extend(self.pixels.iter().flat_map(|(r, g, b)| [*r, *g, *b]));
+        ppm
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Drawing;
+    use super::Raster;
+    use super::Shape;
+    use super::BLACK;
+    use super::BLUE;
+    use super::GREEN;
+    use super::RED;
+    use super::WHITE;
+
+    #[test]
+    fn ppm_has_header_and_pixels() {
+        let mut drawing = Drawing::new(2.0, 1.0, WHITE);
+        drawing.push(Shape::Rect {
+            x: 1.0,
+            y: 0.0,
+            width: 1.0,
+            height: 1.0,
+            fill: RED,
+        });
+        let ppm = drawing.to_ppm();
+        let header = b"P6\n1024 512\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        let pixels = &ppm[header.len()..];
+        assert_eq!(pixels.len(), 1024 * 512 * 3);
+        assert_eq!(&pixels[..3], &[255, 255, 255]);
+        assert_eq!(&pixels[1023 * 3..1024 * 3], &[RED.0, RED.1, RED.2]);
+    }
+
+    #[test]
+    fn polygon_is_filled_inside_only() {
+        let mut raster = Raster::new(10, 10, BLACK);
+        raster.fill_polygon(&[(2.0, 2.0), (8.0, 2.0), (8.0, 8.0), (2.0, 8.0)], WHITE);
+        assert_eq!(raster.pixels[5 * 10 + 5], WHITE);
+        assert_eq!(raster.pixels[2 * 10 + 2], WHITE);
+        assert_eq!(raster.pixels[7 * 10 + 7], WHITE);
+        assert_eq!(raster.pixels[8 * 10 + 8], BLACK);
+        assert_eq!(raster.pixels[5 * 10 + 1], BLACK);
+    }
+
+    #[test]
+    fn shapes_are_clipped_to_canvas() {
+        let mut raster = Raster::new(10, 10, BLACK);
+        raster.fill_rect((-5.0, -5.0), (2.0, 2.0), WHITE);
+        raster.line((-1e12, 5.5), (1e12, 5.5), RED);
+        raster.fill_circle((1e12, 1e12), 1.0, BLUE);
+        assert_eq!(raster.pixels[0], WHITE);
+        assert_eq!(raster.pixels[11], WHITE);
+        assert_eq!(raster.pixels[2 * 10 + 2], BLACK);
+        assert!(raster.pixels[5 * 10..6 * 10].iter().all(|p| *p == RED));
+        assert!(!raster.pixels.contains(&BLUE));
+    }
+
+    #[test]
+    fn svg_contains_every_shape() {
+        let mut drawing = Drawing::new(10.0, 10.0, WHITE);
+        drawing.push(Shape::Circle {
+            x: 5.0,
+            y: 5.0,
+            radius: 1.0,
+            fill: BLUE,
+        });
+        drawing.push(Shape::Line {
+            from: (0.0, 0.0),
+            to: (10.0, 10.0),
+            stroke: GREEN,
+        });
+        let svg = drawing.to_svg();
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.contains(r##"<circle cx="5" cy="5" r="1" fill="#285adc"/>"##));
+        assert!(svg.contains("<line "));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+}
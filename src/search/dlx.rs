@@ -0,0 +1,271 @@
+// Solutions to Advent of Code 2025
+// Copyright (C) 2025  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Exact cover by Knuth's Algorithm X with dancing links.
+
+use std::ops::ControlFlow;
+
+/// A node in the toroidal lists: a column header or a 1 in the matrix.
+struct Node {
+    left: usize,
+    right: usize,
+    up: usize,
+    down: usize,
+    column: usize,
+    row: usize,
+}
+
+/// An exact cover problem: choose rows such that every primary column is covered by exactly one
+/// chosen row and every secondary column by at most one. Columns are numbered with the primary
+/// columns first.
+pub struct ExactCover {
+    /// The root at index 0, then one header per column, then the 1s of each row.
+    nodes: Vec<Node>,
+    sizes: Vec<usize>,
+    num_rows: usize,
+}
+
+impl ExactCover {
+    pub fn new(num_primary: usize, num_secondary: usize) -> Self {
+        let num_columns = num_primary + num_secondary;
+        let mut nodes: Vec<Node> = (0..=num_columns)
+            .map(|h| Node {
+                left: h,
+                right: h,
+                up: h,
+                down: h,
+                column: h.wrapping_sub(1),
+                row: usize::MAX,
+            })
+            .collect();
+        // Only primary columns are linked to the root, so only they are ever chosen to cover.
+        for (h, node) in nodes.iter_mut().enumerate().take(num_primary + 1) {
+            node.right = if h == num_primary { 0 } else { h + 1 };
+            node.left = if h == 0 { num_primary } else { h - 1 };
+        }
+        Self {
+            nodes,
+            sizes: vec![0; num_columns],
+            num_rows: 0,
+        }
+    }
+
+    pub fn num_columns(&self) -> usize {
+        self.sizes.len()
+    }
+
+    /// Add a row with 1s in `columns`, and return its index.
+    pub fn add_row(&mut self, columns: &[usize]) -> usize {
+        let row = self.num_rows;
+        self.num_rows += 1;
+        let first = self.nodes.len();
+        for (i, column) in columns.iter().enumerate() {
+            assert!(*column < self.num_columns(), "No column {column}");
+            let header = column + 1;
+            let node = self.nodes.len();
+            let up = self.nodes[header].up;
+            self.nodes.push(Node {
+                left: if i == 0 { node } else { node - 1 },
+                right: first,
+                up,
+                down: header,
+                column: *column,
+                row,
+            });
+            self.nodes[up].down = node;
+            self.nodes[header].up = node;
+            self.nodes[first].left = node;
+            if i > 0 {
+                self.nodes[node - 1].right = node;
+            }
+            self.sizes[*column] += 1;
+        }
+        row
+    }
+
+    fn cover(&mut self, column: usize) {
+        let header = column + 1;
+        let Node { left, right, .. } = self.nodes[header];
+        self.nodes[right].left = left;
+        self.nodes[left].right = right;
+        let mut i = self.nodes[header].down;
+        while i != header {
+            let mut j = self.nodes[i].right;
+            while j != i {
+                let Node { up, down, .. } = self.nodes[j];
+                self.nodes[down].up = up;
+                self.nodes[up].down = down;
+                self.sizes[self.nodes[j].column] -= 1;
+                j = self.nodes[j].right;
+            }
+            i = self.nodes[i].down;
+        }
+    }
+
+    fn uncover(&mut self, column: usize) {
+        let header = column + 1;
+        let mut i = self.nodes[header].up;
+        while i != header {
+            let mut j = self.nodes[i].left;
+            while j != i {
+                let Node { up, down, .. } = self.nodes[j];
+                self.nodes[down].up = j;
+                self.nodes[up].down = j;
+                self.sizes[self.nodes[j].column] += 1;
+                j = self.nodes[j].left;
+            }
+            i = self.nodes[i].up;
+        }
+        let Node { left, right, .. } = self.nodes[header];
+        self.nodes[right].left = header;
+        self.nodes[left].right = header;
+    }
+
+    fn search_from<F>(&mut self, chosen: &mut Vec<usize>, on_solution: &mut F) -> ControlFlow<()>
+    where
+        F: FnMut(&[usize]) -> ControlFlow<()>,
+    {
+        if self.nodes[0].right == 0 {
+            return on_solution(chosen);
+        }
+        // Cover the primary column with the fewest rows left.
+        let mut header = self.nodes[0].right;
+        let mut best = header;
+        while header != 0 {
+            if self.sizes[header - 1] < self.sizes[best - 1] {
+                best = header;
+            }
+            header = self.nodes[header].right;
+        }
+        let column = best - 1;
+        if self.sizes[column] == 0 {
+            return ControlFlow::Continue(());
+        }
+
+        self.cover(column);
+        let mut result = ControlFlow::Continue(());
+        let mut r = self.nodes[best].down;
+        while r != best && result.is_continue() {
+            chosen.push(self.nodes[r].row);
+            let mut j = self.nodes[r].right;
+            while j != r {
+                self.cover(self.nodes[j].column);
+                j = self.nodes[j].right;
+            }
+            result = self.search_from(chosen, on_solution);
+            let mut j = self.nodes[r].left;
+            while j != r {
+                self.uncover(self.nodes[j].column);
+                j = self.nodes[j].left;
+            }
+            chosen.pop();
+            r = self.nodes[r].down;
+        }
+        self.uncover(column);
+        result
+    }
+
+    /// Call `on_solution` with the rows of each solution, until it returns
+    /// [ControlFlow::Break].
+    pub fn search<F>(&mut self, mut on_solution: F)
+    where
+        F: FnMut(&[usize]) -> ControlFlow<()>,
+    {
+        let _ = self.search_from(&mut Vec::new(), &mut on_solution);
+    }
+
+    /// The rows of the first solution found, if there is one.
+    pub fn first_solution(&mut self) -> Option<Vec<usize>> {
+        let mut solution = None;
+        self.search(|rows| {
+            solution = Some(rows.to_vec());
+            ControlFlow::Break(())
+        });
+        solution
+    }
+
+    pub fn count_solutions(&mut self) -> usize {
+        let mut count = 0;
+        self.search(|_| {
+            count += 1;
+            ControlFlow::Continue(())
+        });
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ExactCover;
+
+    #[test]
+    fn knuth_example() {
+        let mut problem = ExactCover::new(7, 0);
+        for row in [
+            &[2, 4, 5][..],
+            &[0, 3, 6],
+            &[1, 2, 5],
+            &[0, 3],
+            &[1, 6],
+            &[3, 4, 6],
+        ] {
+            problem.add_row(row);
+        }
+        let mut solution = problem.first_solution().unwrap();
+        solution.sort();
+        assert_eq!(solution, vec![0, 3, 4]);
+        assert_eq!(problem.count_solutions(), 1);
+    }
+
+    #[test]
+    fn no_solution() {
+        let mut problem = ExactCover::new(2, 0);
+        problem.add_row(&[0]);
+        assert_eq!(problem.first_solution(), None);
+        assert_eq!(problem.count_solutions(), 0);
+    }
+
+    #[test]
+    fn domino_tilings() {
+        // The 4 by 4 square has 36 domino tilings.
+        let mut problem = ExactCover::new(16, 0);
+        for r in 0..4 {
+            for c in 0..4 {
+                if c < 3 {
+                    problem.add_row(&[r * 4 + c, r * 4 + c + 1]);
+                }
+                if r < 3 {
+                    problem.add_row(&[r * 4 + c, r * 4 + c + 4]);
+                }
+            }
+        }
+        assert_eq!(problem.count_solutions(), 36);
+    }
+
+    #[test]
+    fn queens_use_secondary_columns() {
+        // Ranks and files are primary and must each hold a queen. Diagonals are secondary, since
+        // some have none.
+        let n = 8;
+        let mut problem = ExactCover::new(2 * n, 2 * (2 * n - 1));
+        for r in 0..n {
+            for c in 0..n {
+                problem.add_row(&[r, n + c, 2 * n + r + c, 4 * n - 1 + r + n - 1 - c]);
+            }
+        }
+        assert_eq!(problem.count_solutions(), 92);
+    }
+}
@@ -0,0 +1,370 @@
+// Solutions to Advent of Code 2025
+// Copyright (C) 2025  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Write;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc;
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use adventofcode_2025::common::AocError;
+use adventofcode_2025::days;
+
+const MAX_BODY_LEN: usize = 16 * 1024 * 1024;
+
+/// The longest request line or header line, including its line break.
+const MAX_LINE_LEN: usize = 8 * 1024;
+
+const MAX_HEADERS: usize = 100;
+
+/// How long a solver may run before the client gets an error instead of the answers.
+const SOLVE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The number of solvers running, including those that timed out but have not finished yet.
+static SOLVING: AtomicUsize = AtomicUsize::new(0);
+
+/// How long a client may take to send its request, or to accept each write of the response.
+const IO_TIMEOUT: Duration = Duration::from_secs(10);
+
+struct Request {
+    method: String,
+    path: String,
+    body: String,
+}
+
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn ok(body: String) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            body: format!(r#"{{"status":"error","error":{}}}"#, json_string(message)),
+        }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            431 => "Request Header Fields Too Large",
+            503 => "Service Unavailable",
+            _ => "Internal Server Error",
+        }
+    }
+}
+
+/// Serve the solvers over HTTP on `127.0.0.1:<port>`. Runs until the process is killed.
+///
+/// - `GET /days` lists the registered day numbers.
+/// - `POST /days/<n>` runs day `n` on the request body and returns the answers and timings.
+///
+/// Connections are handled by one worker thread per available CPU. When they are all busy, new
+/// connections wait in the listen backlog. A solver that times out keeps running in the
+/// background, so no more solvers than workers run at once, and requests beyond that are
+/// refused until one finishes.
+pub fn serve(port: u16) -> Result<(), std::io::Error> {
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    eprintln!("Listening on http://{}", listener.local_addr()?);
+
+    let workers = num_workers();
+    let (sender, receiver) = mpsc::sync_channel::<TcpStream>(0);
+    let receiver = Arc::new(Mutex::new(receiver));
+    for _ in 0..workers {
+        let receiver = Arc::clone(&receiver);
+        std::thread::spawn(move || loop {
+            let Ok(stream) = receiver.lock().unwrap().recv() else {
+                return;
+            };
+            if let Err(e) = handle_connection(stream) {
+                eprintln!("Connection error: {e}");
+            }
+        });
+    }
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => sender
+                .send(stream)
+                .expect("Worker threads should outlive the listener"),
+            Err(e) => eprintln!("Failed to accept connection: {e}"),
+        }
+    }
+
+    Ok(())
+}
+
+fn num_workers() -> usize {
+    std::thread::available_parallelism().map_or(4, usize::from)
+}
+
+fn handle_connection(mut stream: TcpStream) -> Result<(), std::io::Error> {
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    let response = match read_request(&mut BufReader::new(&stream)) {
+        Ok(request) => route(&request),
+        Err(response) => response,
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.reason(),
+        response.body.len(),
+        response.body,
+    )?;
+    stream.flush()
+}
+
+/// Read a line of at most [MAX_LINE_LEN] bytes, or fail with `too_long` if it is longer.
+fn read_line<R: BufRead>(reader: &mut R, too_long: Response) -> Result<String, Response> {
+    let mut line = String::new();
+    reader
+        .take(MAX_LINE_LEN.try_into().unwrap_or(u64::MAX))
+        .read_line(&mut line)
+        .map_err(|_| Response::error(400, "Malformed request"))?;
+    if line.ends_with('\n') {
+        Ok(line)
+    } else if line.len() >= MAX_LINE_LEN {
+        Err(too_long)
+    } else {
+        Err(Response::error(400, "Incomplete request"))
+    }
+}
+
+fn read_request<R: BufRead>(reader: &mut R) -> Result<Request, Response> {
+    let bad_request = |_| Response::error(400, "Malformed request");
+
+    let request_line = read_line(reader, Response::error(400, "Request line too long"))?;
+    let mut parts = request_line.split_ascii_whitespace();
+    let (method, path) = parts
+        .next()
+        .zip(parts.next())
+        .ok_or_else(|| Response::error(400, "Malformed request line"))?;
+
+    let mut content_length = 0;
+    for num_headers in 0.. {
+        let header = read_line(reader, Response::error(431, "Header line too long"))?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        } else if num_headers == MAX_HEADERS {
+            return Err(Response::error(431, "Too many headers"));
+        } else if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| Response::error(400, "Invalid Content-Length"))?;
+            }
+        }
+    }
+
+    if content_length > MAX_BODY_LEN {
+        return Err(Response::error(413, "Request body too large"));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).map_err(bad_request)?;
+
+    Ok(Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        body: String::from_utf8(body)
+            .map_err(|_| Response::error(400, "Request body is not valid UTF-8"))?,
+    })
+}
+
+fn route(request: &Request) -> Response {
+    let segments: Vec<&str> = request.path.split('/').filter(|s| !s.is_empty()).collect();
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["days"]) => list_days(),
+        ("POST", ["days", day]) => match day.parse::<u8>() {
+            Ok(day) => run_day(day, &request.body),
+            Err(_) => Response::error(400, &format!("Invalid day number: {day}")),
+        },
+        (_, ["days"]) | (_, ["days", _]) => Response::error(405, "Method not allowed"),
+        _ => Response::error(404, "Not found"),
+    }
+}
+
+fn list_days() -> Response {
+    let days: Vec<String> = days::all_numbers()
+        .into_iter()
+        .map(|day| day.to_string())
+        .collect();
+    Response::ok(format!(r#"{{"days":[{}]}}"#, days.join(",")))
+}
+
+fn run_day(day: u8, input: &str) -> Response {
+    if SOLVING.fetch_add(1, Ordering::SeqCst) >= num_workers() {
+        SOLVING.fetch_sub(1, Ordering::SeqCst);
+        return Response::error(503, "Too many solvers running");
+    }
+
+    let lines: Vec<String> = input.lines().map(&str::to_string).collect();
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let start = Instant::now();
+        let result =
+            std::panic::catch_unwind(|| days::try_solve(day, days::DEFAULT_VARIANT, &lines));
+        let _ = tx.send((result, start.elapsed()));
+        SOLVING.fetch_sub(1, Ordering::SeqCst);
+    });
+
+    match rx.recv_timeout(SOLVE_TIMEOUT) {
+        Ok((Ok(Err(e @ AocError::UnknownDay(_))), _)) => Response::error(404, &e.to_string()),
+        Ok((Ok(Err(e)), _)) => Response::error(400, &e.to_string()),
+        Ok((Ok(Ok((a, b))), elapsed)) => Response::ok(format!(
+            r#"{{"day":{day},"status":"ok","a":{},"b":{},"timings":{{"total_us":{}}}}}"#,
+            json_string(&a),
+            json_string(&b),
+            elapsed.as_micros(),
+        )),
+        Ok((Err(_), _)) | Err(RecvTimeoutError::Disconnected) => {
+            Response::error(500, &format!("Solver for day {day} panicked"))
+        }
+        Err(RecvTimeoutError::Timeout) => Response::error(
+            503,
+            &AocError::Timeout {
+                day,
+                limit: SOLVE_TIMEOUT,
+            }
+            .to_string(),
+        ),
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => result.push_str(r#"\""#),
+            '\\' => result.push_str(r"\\"),
+            '\n' => result.push_str(r"\n"),
+            '\r' => result.push_str(r"\r"),
+            '\t' => result.push_str(r"\t"),
+            ch if ch.is_control() => result.push_str(&format!(r"\u{:04x}", u32::from(ch))),
+            ch => result.push(ch),
+        }
+    }
+    result.push('"');
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::json_string;
+    use super::read_request;
+    use super::route;
+
+    fn request(raw: &str) -> super::Response {
+        match read_request(&mut raw.as_bytes()) {
+            Ok(request) => route(&request),
+            Err(response) => response,
+        }
+    }
+
+    #[test]
+    fn json_string_escapes() {
+        assert_eq!(json_string(r#"a"b\c"#), r#""a\"b\\c""#);
+        assert_eq!(json_string("a\nb\u{1}"), r#""a\nb\u0001""#);
+    }
+
+    #[test]
+    fn lists_days() {
+        let response = request("GET /days HTTP/1.1\r\nHost: localhost\r\n\r\n");
+        assert_eq!(response.status, 200);
+        assert!(response.body.starts_with(r#"{"days":[1,2,"#));
+    }
+
+    #[test]
+    fn solves_day() {
+        let body = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+        let response = request(&format!(
+            "POST /days/1 HTTP/1.1\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        ));
+        assert_eq!(response.status, 200);
+        assert!(
+            response
+                .body
+                .starts_with(r#"{"day":1,"status":"ok","a":"3","b":"6","#),
+            "{}",
+            response.body
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_day() {
+        assert_eq!(request("POST /days/26 HTTP/1.1\r\n\r\n").status, 404);
+        assert_eq!(request("POST /days/x HTTP/1.1\r\n\r\n").status, 400);
+        assert_eq!(request("GET /days/1 HTTP/1.1\r\n\r\n").status, 405);
+        assert_eq!(request("GET / HTTP/1.1\r\n\r\n").status, 404);
+    }
+
+    #[test]
+    fn rejects_oversized_headers() {
+        let long = "x".repeat(super::MAX_LINE_LEN);
+        assert_eq!(
+            request(&format!("GET /{long} HTTP/1.1\r\n\r\n")).status,
+            400
+        );
+        assert_eq!(
+            request(&format!("GET /days HTTP/1.1\r\nX: {long}\r\n\r\n")).status,
+            431
+        );
+        let many = "X: y\r\n".repeat(super::MAX_HEADERS + 1);
+        assert_eq!(
+            request(&format!("GET /days HTTP/1.1\r\n{many}\r\n")).status,
+            431
+        );
+        let enough = "X: y\r\n".repeat(super::MAX_HEADERS);
+        assert_eq!(
+            request(&format!("GET /days HTTP/1.1\r\n{enough}\r\n")).status,
+            200
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        let body = "X68\n";
+        let response = request(&format!(
+            "POST /days/1 HTTP/1.1\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        ));
+        assert_eq!(response.status, 400);
+        assert!(response.body.contains("Line 1: Invalid rotation"));
+    }
+}
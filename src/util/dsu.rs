@@ -0,0 +1,162 @@
+// Solutions to Advent of Code 2025
+// Copyright (C) 2025  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Disjoint sets, also known as union-find.
+
+/// A partition of the elements `0..len` into disjoint sets, with path compression and union by
+/// size, so that every operation takes nearly constant amortised time.
+#[derive(Clone, Debug)]
+pub struct Dsu {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    num_components: usize,
+}
+
+impl Dsu {
+    /// Each of the `len` elements in a set of its own.
+    pub fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+            num_components: len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// The representative of the set containing `x`.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut x = x;
+        while self.parent[x] != root {
+            let next = self.parent[x];
+            self.parent[x] = root;
+            x = next;
+        }
+        root
+    }
+
+    /// Merge the sets containing `a` and `b`, and return whether they were different.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (big, small) = if self.size[a] >= self.size[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parent[small] = big;
+        self.size[big] += self.size[small];
+        self.num_components -= 1;
+        true
+    }
+
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// The size of the set containing `x`.
+    pub fn size_of(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    pub fn num_components(&self) -> usize {
+        self.num_components
+    }
+
+    /// The size of each set, in order of their smallest elements.
+    pub fn component_sizes(&mut self) -> Vec<usize> {
+        self.components().iter().map(Vec::len).collect()
+    }
+
+    /// The elements of each set in increasing order, with the sets in order of their smallest
+    /// elements.
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut index = vec![usize::MAX; self.len()];
+        let mut components: Vec<Vec<usize>> = Vec::with_capacity(self.num_components);
+        for x in 0..self.len() {
+            let root = self.find(x);
+            if index[root] == usize::MAX {
+                index[root] = components.len();
+                components.push(Vec::new());
+            }
+            components[index[root]].push(x);
+        }
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generate::Rng;
+
+    use super::Dsu;
+
+    #[test]
+    fn union_merges_sets() {
+        let mut dsu = Dsu::new(6);
+        assert_eq!(dsu.num_components(), 6);
+        assert!(dsu.union(0, 1));
+        assert!(dsu.union(2, 3));
+        assert!(dsu.union(1, 3));
+        assert!(!dsu.union(0, 2));
+        assert!(dsu.same(0, 3));
+        assert!(!dsu.same(0, 4));
+        assert_eq!(dsu.size_of(2), 4);
+        assert_eq!(dsu.num_components(), 3);
+        assert_eq!(dsu.components(), vec![vec![0, 1, 2, 3], vec![4], vec![5]]);
+        assert_eq!(dsu.component_sizes(), vec![4, 1, 1]);
+    }
+
+    #[test]
+    fn matches_naive_labels() {
+        let mut rng = Rng::new(0);
+        let n = 50;
+        let mut dsu = Dsu::new(n);
+        let mut labels: Vec<usize> = (0..n).collect();
+        for _ in 0..100 {
+            let (a, b) = (rng.range(0, n - 1), rng.range(0, n - 1));
+            let merged = labels[a] != labels[b];
+            assert_eq!(dsu.union(a, b), merged);
+            let (from, to) = (labels[b], labels[a]);
+            labels
+                .iter_mut()
+                .filter(|l| **l == from)
+                .for_each(|l| *l = to);
+
+            for x in 0..n {
+                let size = labels.iter().filter(|l| **l == labels[x]).count();
+                assert_eq!(dsu.size_of(x), size);
+            }
+            let mut distinct = labels.clone();
+            distinct.sort();
+            distinct.dedup();
+            assert_eq!(dsu.num_components(), distinct.len());
+        }
+    }
+}
@@ -0,0 +1,300 @@
+// Solutions to Advent of Code 2025
+// Copyright (C) 2025  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Linear algebra over GF(2), the field of integers modulo 2, where addition is XOR.
+
+use std::ops::BitXorAssign;
+
+/// A vector over GF(2), packed 64 bits to a word.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct BitVector {
+    len: usize,
+    words: Vec<u64>,
+}
+
+impl BitVector {
+    pub fn zeros(len: usize) -> Self {
+        Self {
+            len,
+            words: vec![0; len.div_ceil(64)],
+        }
+    }
+
+    /// The vector of length `len` whose bits at `ones` are set.
+    pub fn from_ones<I: IntoIterator<Item = usize>>(len: usize, ones: I) -> Self {
+        let mut v = Self::zeros(len);
+        for i in ones {
+            v.set(i, true);
+        }
+        v
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, i: usize) -> bool {
+        assert!(i < self.len, "Bit {i} out of range for length {}", self.len);
+        self.words[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    pub fn set(&mut self, i: usize, bit: bool) {
+        assert!(i < self.len, "Bit {i} out of range for length {}", self.len);
+        if bit {
+            self.words[i / 64] |= 1 << (i % 64);
+        } else {
+            self.words[i / 64] &= !(1 << (i % 64));
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    /// The number of set bits, also known as the Hamming weight.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// The indices of the set bits, in increasing order.
+    pub fn ones(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len).filter(|i| self.get(*i))
+    }
+
+    /// The dot product with `other`: the parity of the bits set in both.
+    pub fn dot(&self, other: &BitVector) -> bool {
+        assert_eq!(self.len, other.len);
+        self.words
+            .iter()
+            .zip(&other.words)
+            .map(|(a, b)| (a & b).count_ones())
+            .sum::<u32>()
+            % 2
+            == 1
+    }
+}
+
+impl BitXorAssign<&BitVector> for BitVector {
+    fn bitxor_assign(&mut self, rhs: &BitVector) {
+        assert_eq!(self.len, rhs.len);
+        for (a, b) in self.words.iter_mut().zip(&rhs.words) {
+            *a ^= b;
+        }
+    }
+}
+
+/// A matrix over GF(2), stored as a list of rows.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BitMatrix {
+    cols: usize,
+    rows: Vec<BitVector>,
+}
+
+impl BitMatrix {
+    /// An empty matrix with `cols` columns and no rows.
+    pub fn new(cols: usize) -> Self {
+        Self {
+            cols,
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn push_row(&mut self, row: BitVector) {
+        assert_eq!(row.len(), self.cols, "Row has wrong length");
+        self.rows.push(row);
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn row(&self, r: usize) -> &BitVector {
+        &self.rows[r]
+    }
+
+    /// The product `Ax` of this matrix `A` and the column vector `x`.
+    pub fn mul_vector(&self, x: &BitVector) -> BitVector {
+        BitVector::from_ones(
+            self.rows.len(),
+            (0..self.rows.len()).filter(|r| self.rows[*r].dot(x)),
+        )
+    }
+
+    /// Bring the matrix to reduced row echelon form in place, and return the pivot column of each
+    /// nonzero row. Row `i` of the result has its leading one in column `pivots[i]`, and that
+    /// column is zero in every other row.
+    pub fn row_reduce(&mut self) -> Vec<usize> {
+        let mut pivots = Vec::new();
+        for col in 0..self.cols {
+            let rank = pivots.len();
+            let Some(pivot_row) = (rank..self.rows.len()).find(|r| self.rows[*r].get(col)) else {
+                continue;
+            };
+            self.rows.swap(rank, pivot_row);
+            let pivot = self.rows[rank].clone();
+            for (r, row) in self.rows.iter_mut().enumerate() {
+                if r != rank && row.get(col) {
+                    *row ^= &pivot;
+                }
+            }
+            pivots.push(col);
+        }
+        pivots
+    }
+
+    pub fn rank(&self) -> usize {
+        self.clone().row_reduce().len()
+    }
+
+    /// A solution `x` of `Ax = rhs`, with every free variable set to zero, or `None` if there is
+    /// no solution.
+    pub fn solve(&self, rhs: &BitVector) -> Option<BitVector> {
+        assert_eq!(
+            rhs.len(),
+            self.rows.len(),
+            "Right-hand side has wrong length"
+        );
+        let mut augmented = BitMatrix::new(self.cols + 1);
+        for (r, row) in self.rows.iter().enumerate() {
+            let mut row = BitVector::from_ones(self.cols + 1, row.ones());
+            row.set(self.cols, rhs.get(r));
+            augmented.push_row(row);
+        }
+
+        let pivots = augmented.row_reduce();
+        if pivots.last() == Some(&self.cols) {
+            return None;
+        }
+        Some(BitVector::from_ones(
+            self.cols,
+            pivots
+                .iter()
+                .zip(&augmented.rows)
+                .filter(|(_, row)| row.get(self.cols))
+                .map(|(pivot, _)| *pivot),
+        ))
+    }
+
+    /// A basis of the null space: the vectors `x` such that `Ax = 0`. There is one basis vector
+    /// per free variable, so the basis has `num_cols() - rank()` vectors.
+    pub fn null_space(&self) -> Vec<BitVector> {
+        let mut reduced = self.clone();
+        let pivots = reduced.row_reduce();
+        let mut pivot_cols = pivots.iter().peekable();
+        (0..self.cols)
+            .filter(|col| {
+                if pivot_cols.peek() == Some(&col) {
+                    pivot_cols.next();
+                    false
+                } else {
+                    true
+                }
+            })
+            .map(|free| {
+                let mut v = BitVector::from_ones(self.cols, [free]);
+                for (pivot, row) in pivots.iter().zip(&reduced.rows) {
+                    v.set(*pivot, row.get(free));
+                }
+                v
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generate::Rng;
+
+    use super::BitMatrix;
+    use super::BitVector;
+
+    fn matrix(cols: usize, rows: &[&[usize]]) -> BitMatrix {
+        let mut m = BitMatrix::new(cols);
+        for row in rows {
+            m.push_row(BitVector::from_ones(cols, row.iter().copied()));
+        }
+        m
+    }
+
+    fn random_matrix(rng: &mut Rng, rows: usize, cols: usize) -> BitMatrix {
+        let mut m = BitMatrix::new(cols);
+        for _ in 0..rows {
+            m.push_row(BitVector::from_ones(
+                cols,
+                (0..cols).filter(|_| rng.chance(40)),
+            ));
+        }
+        m
+    }
+
+    #[test]
+    fn bit_vector_spans_words() {
+        let mut v = BitVector::from_ones(130, [0, 63, 64, 129]);
+        assert_eq!(v.count_ones(), 4);
+        assert_eq!(v.ones().collect::<Vec<_>>(), vec![0, 63, 64, 129]);
+        v ^= &BitVector::from_ones(130, [63, 100]);
+        assert_eq!(v.ones().collect::<Vec<_>>(), vec![0, 64, 100, 129]);
+        assert!(!v.is_zero());
+    }
+
+    #[test]
+    fn rank_of_dependent_rows() {
+        assert_eq!(matrix(3, &[&[0, 1], &[1, 2], &[0, 2]]).rank(), 2);
+        assert_eq!(matrix(3, &[&[0], &[1], &[2]]).rank(), 3);
+        assert_eq!(matrix(3, &[&[], &[]]).rank(), 0);
+    }
+
+    #[test]
+    fn solve_inconsistent_is_none() {
+        let m = matrix(3, &[&[0, 1], &[1, 2], &[0, 2]]);
+        assert_eq!(m.solve(&BitVector::from_ones(3, [0])), None);
+        assert!(m.solve(&BitVector::from_ones(3, [0, 1])).is_some());
+    }
+
+    #[test]
+    fn random_systems() {
+        for seed in 0..200 {
+            let mut rng = Rng::new(seed);
+            let rows = rng.range(1, 12);
+            let cols = rng.range(1, 80);
+            let m = random_matrix(&mut rng, rows, cols);
+            let x = BitVector::from_ones(cols, (0..cols).filter(|_| rng.chance(50)));
+            let rhs = m.mul_vector(&x);
+
+            let solution = m.solve(&rhs).expect("System constructed to be solvable");
+            assert_eq!(m.mul_vector(&solution), rhs, "seed={seed}");
+
+            let null_space = m.null_space();
+            assert_eq!(null_space.len(), cols - m.rank(), "seed={seed}");
+            for v in &null_space {
+                assert!(m.mul_vector(v).is_zero(), "seed={seed}");
+            }
+            let mut basis = BitMatrix::new(cols);
+            for v in null_space.iter().cloned() {
+                basis.push_row(v);
+            }
+            assert_eq!(basis.rank(), null_space.len(), "seed={seed}");
+        }
+    }
+}
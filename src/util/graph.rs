@@ -0,0 +1,392 @@
+// Solutions to Advent of Code 2025
+// Copyright (C) 2025  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Directed graphs with named nodes.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// The index of a node in a [Graph], in the order the nodes were first named.
+pub type NodeId = usize;
+
+/// Why [Graph::count_paths] could not count the paths between two nodes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PathCountError {
+    /// A node on a cycle reachable from the start, so there may be infinitely many paths.
+    Cycle(NodeId),
+    /// There are at least 2^128 paths.
+    Overflow,
+}
+
+/// A directed graph whose nodes are identified by name. Each name is interned to a [NodeId] the
+/// first time it is seen, and edges are stored as adjacency lists of IDs.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Graph {
+    names: Vec<String>,
+    ids: HashMap<String, NodeId>,
+    edges: Vec<Vec<NodeId>>,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The ID of the node named `name`, adding it if there is none.
+    pub fn intern(&mut self, name: &str) -> NodeId {
+        if let Some(id) = self.ids.get(name) {
+            *id
+        } else {
+            let id = self.names.len();
+            self.names.push(name.to_string());
+            self.ids.insert(name.to_string(), id);
+            self.edges.push(Vec::new());
+            id
+        }
+    }
+
+    /// The ID of the node named `name`, if there is one.
+    pub fn id(&self, name: &str) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId) {
+        self.edges[from].push(to);
+    }
+
+    /// The nodes with an edge from `id`, in the order the edges were added.
+    pub fn neighbors(&self, id: NodeId) -> &[NodeId] {
+        &self.edges[id]
+    }
+
+    /// Depth-first search from each of `starts`, returning the visited nodes in reverse postorder,
+    /// or a node on a cycle if one is found.
+    fn reverse_postorder<I>(&self, starts: I) -> Result<Vec<NodeId>, NodeId>
+    where
+        I: IntoIterator<Item = NodeId>,
+    {
+        const UNVISITED: u8 = 0;
+        const ON_STACK: u8 = 1;
+        const FINISHED: u8 = 2;
+        let mut state = vec![UNVISITED; self.len()];
+        let mut postorder = Vec::new();
+        for start in starts {
+            if state[start] != UNVISITED {
+                continue;
+            }
+            state[start] = ON_STACK;
+            let mut stack: Vec<(NodeId, usize)> = vec![(start, 0)];
+            while let Some((node, i)) = stack.last_mut() {
+                let node = *node;
+                if let Some(next) = self.edges[node].get(*i) {
+                    *i += 1;
+                    match state[*next] {
+                        UNVISITED => {
+                            state[*next] = ON_STACK;
+                            stack.push((*next, 0));
+                        }
+                        ON_STACK => return Err(*next),
+                        _ => {}
+                    }
+                } else {
+                    state[node] = FINISHED;
+                    postorder.push(node);
+                    stack.pop();
+                }
+            }
+        }
+        postorder.reverse();
+        Ok(postorder)
+    }
+
+    /// Every node in topological order, where each node comes before all nodes it has edges to,
+    /// or a node on a cycle if there is one.
+    pub fn topological_order(&self) -> Result<Vec<NodeId>, NodeId> {
+        self.reverse_postorder(0..self.len())
+    }
+
+    /// The nodes reachable from `from` in topological order, or a node on a cycle reachable from
+    /// `from` if there is one.
+    pub fn topological_order_from(&self, from: NodeId) -> Result<Vec<NodeId>, NodeId> {
+        self.reverse_postorder([from])
+    }
+
+    /// Whether each node is reachable from `from`, indexed by ID. Every node reaches itself.
+    pub fn reachable(&self, from: NodeId) -> Vec<bool> {
+        let mut reached = vec![false; self.len()];
+        reached[from] = true;
+        let mut stack = vec![from];
+        while let Some(node) = stack.pop() {
+            for next in &self.edges[node] {
+                if !reached[*next] {
+                    reached[*next] = true;
+                    stack.push(*next);
+                }
+            }
+        }
+        reached
+    }
+
+    /// The strongly connected components by Tarjan's algorithm. Components are listed in reverse
+    /// topological order: no component has an edge to a component listed after it.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<NodeId>> {
+        let mut index = vec![usize::MAX; self.len()];
+        let mut lowlink = vec![0; self.len()];
+        let mut on_stack = vec![false; self.len()];
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+        let mut next_index = 0;
+
+        for root in 0..self.len() {
+            if index[root] != usize::MAX {
+                continue;
+            }
+            let mut calls: Vec<(NodeId, usize)> = vec![(root, 0)];
+            while let Some((node, i)) = calls.last_mut() {
+                let node = *node;
+                if index[node] == usize::MAX {
+                    index[node] = next_index;
+                    lowlink[node] = next_index;
+                    next_index += 1;
+                    stack.push(node);
+                    on_stack[node] = true;
+                }
+                if let Some(next) = self.edges[node].get(*i).copied() {
+                    *i += 1;
+                    if index[next] == usize::MAX {
+                        calls.push((next, 0));
+                    } else if on_stack[next] {
+                        lowlink[node] = lowlink[node].min(index[next]);
+                    }
+                } else {
+                    calls.pop();
+                    if let Some((caller, _)) = calls.last() {
+                        lowlink[*caller] = lowlink[*caller].min(lowlink[node]);
+                    }
+                    if lowlink[node] == index[node] {
+                        let mut component = Vec::new();
+                        while let Some(member) = stack.pop() {
+                            on_stack[member] = false;
+                            component.push(member);
+                            if member == node {
+                                break;
+                            }
+                        }
+                        components.push(component);
+                    }
+                }
+            }
+        }
+        components
+    }
+
+    /// The number of distinct paths from `from` to `to`, counted in topological order. Fails if
+    /// a cycle is reachable from `from`, or if there are too many paths to `to` to count. Counts
+    /// that overflow at other nodes are not an error.
+    pub fn count_paths(&self, from: NodeId, to: NodeId) -> Result<u128, PathCountError> {
+        let mut counts: Vec<Option<u128>> = vec![Some(0); self.len()];
+        counts[from] = Some(1);
+        for node in self
+            .topological_order_from(from)
+            .map_err(PathCountError::Cycle)?
+        {
+            for next in &self.edges[node] {
+                counts[*next] = counts[*next]
+                    .zip(counts[node])
+                    .and_then(|(a, b)| a.checked_add(b));
+            }
+        }
+        counts[to].ok_or(PathCountError::Overflow)
+    }
+
+    /// The graph in the Graphviz DOT language.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph {\n");
+        for (node, name) in self.names.iter().enumerate() {
+            if self.edges[node].is_empty() {
+                writeln!(dot, "    {name:?};").unwrap();
+            }
+            for next in &self.edges[node] {
+                writeln!(dot, "    {name:?} -> {:?};", self.names[*next]).unwrap();
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Graph;
+    use super::NodeId;
+    use super::PathCountError;
+
+    fn graph(edges: &[(&str, &str)]) -> Graph {
+        let mut graph = Graph::new();
+        for (from, to) in edges {
+            let from = graph.intern(from);
+            let to = graph.intern(to);
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+
+    fn names(graph: &Graph, ids: &[NodeId]) -> Vec<String> {
+        ids.iter().map(|id| graph.name(*id).to_string()).collect()
+    }
+
+    #[test]
+    fn intern_is_idempotent() {
+        let mut graph = Graph::new();
+        let a = graph.intern("a");
+        let b = graph.intern("b");
+        assert_eq!(graph.intern("a"), a);
+        assert_ne!(a, b);
+        assert_eq!(graph.id("b"), Some(b));
+        assert_eq!(graph.id("c"), None);
+        assert_eq!(graph.name(b), "b");
+        assert_eq!(graph.len(), 2);
+    }
+
+    #[test]
+    fn topological_order_respects_edges() {
+        let graph = graph(&[("d", "b"), ("a", "b"), ("b", "c"), ("a", "c"), ("d", "a")]);
+        let order = graph.topological_order().unwrap();
+        assert_eq!(order.len(), graph.len());
+        let position = |id: NodeId| order.iter().position(|o| *o == id).unwrap();
+        for from in 0..graph.len() {
+            for to in graph.neighbors(from) {
+                assert!(position(from) < position(*to));
+            }
+        }
+    }
+
+    #[test]
+    fn topological_order_finds_cycle() {
+        let graph = graph(&[("a", "b"), ("b", "c"), ("c", "b"), ("x", "a")]);
+        let cycle_node = graph.topological_order().unwrap_err();
+        assert!(["b", "c"].contains(&graph.name(cycle_node)));
+        assert!(graph
+            .topological_order_from(graph.id("c").unwrap())
+            .is_err());
+
+        let acyclic = self::graph(&[("a", "b"), ("c", "c")]);
+        assert_eq!(
+            names(&acyclic, &acyclic.topological_order_from(0).unwrap()),
+            vec!["a", "b"]
+        );
+    }
+
+    #[test]
+    fn reachable_follows_edges() {
+        let graph = graph(&[("a", "b"), ("b", "c"), ("d", "a")]);
+        assert_eq!(graph.reachable(0), vec![true, true, true, false]);
+        assert_eq!(graph.reachable(2), vec![false, false, true, false]);
+    }
+
+    #[test]
+    fn strongly_connected_components() {
+        let graph = graph(&[
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "a"),
+            ("c", "d"),
+            ("d", "e"),
+            ("e", "d"),
+            ("f", "e"),
+        ]);
+        let components: Vec<Vec<String>> = graph
+            .strongly_connected_components()
+            .iter()
+            .map(|component| {
+                let mut names = names(&graph, component);
+                names.sort();
+                names
+            })
+            .collect();
+        assert_eq!(
+            components,
+            vec![vec!["d", "e"], vec!["a", "b", "c"], vec!["f"]]
+        );
+    }
+
+    #[test]
+    fn count_paths_through_diamonds() {
+        let mut edges = Vec::new();
+        let names: Vec<String> = (0..=300).map(|i| i.to_string()).collect();
+        for i in (0..300).step_by(3) {
+            edges.push((names[i].as_str(), names[i + 1].as_str()));
+            edges.push((names[i].as_str(), names[i + 2].as_str()));
+            edges.push((names[i + 1].as_str(), names[i + 3].as_str()));
+            edges.push((names[i + 2].as_str(), names[i + 3].as_str()));
+        }
+        let graph = graph(&edges);
+        let from = graph.id("0").unwrap();
+        let to = graph.id("300").unwrap();
+        assert_eq!(graph.count_paths(from, to), Ok(1 << 100));
+        assert_eq!(graph.count_paths(to, from), Ok(0));
+    }
+
+    #[test]
+    fn count_paths_reports_overflow() {
+        let mut edges = Vec::new();
+        let names: Vec<String> = (0..=390).map(|i| i.to_string()).collect();
+        for i in (0..390).step_by(3) {
+            edges.push((names[i].as_str(), names[i + 1].as_str()));
+            edges.push((names[i].as_str(), names[i + 2].as_str()));
+            edges.push((names[i + 1].as_str(), names[i + 3].as_str()));
+            edges.push((names[i + 2].as_str(), names[i + 3].as_str()));
+        }
+        edges.push(("0", "short"));
+        let graph = graph(&edges);
+        let from = graph.id("0").unwrap();
+        let to = graph.id("390").unwrap();
+        assert_eq!(graph.count_paths(from, to), Err(PathCountError::Overflow));
+        assert_eq!(graph.count_paths(from, graph.id("short").unwrap()), Ok(1));
+        assert_eq!(
+            graph.count_paths(from, graph.id("381").unwrap()),
+            Ok(1 << 127)
+        );
+    }
+
+    #[test]
+    fn count_paths_rejects_cycle() {
+        let graph = graph(&[("a", "b"), ("b", "a"), ("a", "c")]);
+        assert_eq!(graph.count_paths(0, 2), Err(PathCountError::Cycle(0)));
+    }
+
+    #[test]
+    fn to_dot() {
+        let mut graph = graph(&[("a", "b"), ("a", "c d")]);
+        graph.intern("e");
+        assert_eq!(
+            graph.to_dot(),
+            "digraph {\n    \"a\" -> \"b\";\n    \"a\" -> \"c d\";\n    \"b\";\n    \"c d\";\n    \"e\";\n}\n"
+        );
+    }
+}
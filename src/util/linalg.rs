@@ -0,0 +1,683 @@
+// Solutions to Advent of Code 2025
+// Copyright (C) 2025  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Exact linear algebra over the rationals, for systems `Ax = b` with small integer coefficients.
+
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::ops::Add;
+use std::ops::Div;
+use std::ops::Mul;
+use std::ops::Neg;
+use std::ops::Sub;
+
+use crate::util::gcd;
+use crate::util::lcm;
+
+/// `a / b` rounded down.
+fn div_floor(a: i64, b: i64) -> i64 {
+    if b < 0 {
+        (-a).div_euclid(-b)
+    } else {
+        a.div_euclid(b)
+    }
+}
+
+/// `a / b` rounded up.
+fn div_ceil(a: i64, b: i64) -> i64 {
+    -div_floor(-a, b)
+}
+
+/// A fraction in lowest terms with a positive denominator.
+///
+/// Arithmetic panics if a numerator or denominator does not fit in an `i64`. The systems solved
+/// here have coefficients 0 and 1 and a few dozen variables, which keeps them far smaller.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Rational {
+    num: i64,
+    den: i64,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { num: 0, den: 1 };
+    pub const ONE: Rational = Rational { num: 1, den: 1 };
+
+    pub fn new(num: i64, den: i64) -> Self {
+        assert_ne!(den, 0, "Zero denominator");
+        let g = gcd(num, den).abs() * den.signum();
+        Self {
+            num: num / g,
+            den: den / g,
+        }
+    }
+
+    pub fn numer(self) -> i64 {
+        self.num
+    }
+
+    pub fn denom(self) -> i64 {
+        self.den
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.num == 0
+    }
+
+    /// The value as an integer, if it is one.
+    pub fn to_integer(self) -> Option<i64> {
+        (self.den == 1).then_some(self.num)
+    }
+}
+
+/// The result of checked arithmetic on a numerator or denominator.
+fn in_range(n: Option<i64>) -> i64 {
+    n.expect("Rational arithmetic overflowed an i64")
+}
+
+impl From<i64> for Rational {
+    fn from(n: i64) -> Self {
+        Self { num: n, den: 1 }
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+    fn add(self, rhs: Rational) -> Rational {
+        let g = gcd(self.den, rhs.den);
+        let num = self
+            .num
+            .checked_mul(rhs.den / g)
+            .zip(rhs.num.checked_mul(self.den / g))
+            .and_then(|(a, b)| a.checked_add(b));
+        Rational::new(in_range(num), in_range((self.den / g).checked_mul(rhs.den)))
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+    fn sub(self, rhs: Rational) -> Rational {
+        self + -rhs
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+    fn mul(self, rhs: Rational) -> Rational {
+        let g1 = gcd(self.num, rhs.den);
+        let g2 = gcd(rhs.num, self.den);
+        Rational::new(
+            in_range((self.num / g1).checked_mul(rhs.num / g2)),
+            in_range((self.den / g2).checked_mul(rhs.den / g1)),
+        )
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+    fn div(self, rhs: Rational) -> Rational {
+        assert!(!rhs.is_zero(), "Division by zero");
+        self * Rational::new(rhs.den, rhs.num)
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+    fn neg(self) -> Rational {
+        Rational {
+            num: in_range(self.num.checked_neg()),
+            den: self.den,
+        }
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (i128::from(self.num) * i128::from(other.den))
+            .cmp(&(i128::from(other.num) * i128::from(self.den)))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+/// The system `Ax = b` in reduced row echelon form. Row `i` says that `x[pivots[i]]` plus the sum
+/// of `rows[i][j] * x[j]` over the free variables `j` equals `rhs[i]`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rref {
+    rows: Vec<Vec<Rational>>,
+    rhs: Vec<Rational>,
+    pivots: Vec<usize>,
+    free: Vec<usize>,
+}
+
+impl Rref {
+    /// Reduce the system with coefficient rows `a` and right-hand side `b` by Gauss-Jordan
+    /// elimination, or return `None` if it has no solution.
+    pub fn new(a: &[Vec<i64>], b: &[i64]) -> Option<Self> {
+        assert_eq!(a.len(), b.len(), "Expected one right-hand side per row");
+        let num_vars = a.first().map(Vec::len).unwrap_or(0);
+        let mut rows: Vec<Vec<Rational>> = a
+            .iter()
+            .map(|row| {
+                assert_eq!(row.len(), num_vars, "Rows have different lengths");
+                row.iter().copied().map(Rational::from).collect()
+            })
+            .collect();
+        let mut rhs: Vec<Rational> = b.iter().copied().map(Rational::from).collect();
+
+        let mut pivots = Vec::new();
+        let mut free = Vec::new();
+        for col in 0..num_vars {
+            let rank = pivots.len();
+            let Some(pivot_row) = (rank..rows.len()).find(|r| !rows[*r][col].is_zero()) else {
+                free.push(col);
+                continue;
+            };
+            rows.swap(rank, pivot_row);
+            rhs.swap(rank, pivot_row);
+            let p = rows[rank][col];
+            rows[rank].iter_mut().for_each(|c| *c = *c / p);
+            rhs[rank] = rhs[rank] / p;
+
+            let pivot = rows[rank].clone();
+            for r in (0..rows.len()).filter(|r| *r != rank) {
+                let factor = rows[r][col];
+                if !factor.is_zero() {
+                    for (c, pc) in rows[r].iter_mut().zip(&pivot) {
+                        *c = *c - factor * *pc;
+                    }
+                    rhs[r] = rhs[r] - factor * rhs[rank];
+                }
+            }
+            pivots.push(col);
+        }
+
+        if rhs[pivots.len()..].iter().any(|r| !r.is_zero()) {
+            return None;
+        }
+        rows.truncate(pivots.len());
+        rhs.truncate(pivots.len());
+        Some(Self {
+            rows,
+            rhs,
+            pivots,
+            free,
+        })
+    }
+
+    pub fn num_vars(&self) -> usize {
+        self.pivots.len() + self.free.len()
+    }
+
+    pub fn rank(&self) -> usize {
+        self.pivots.len()
+    }
+
+    /// The pivot column of each row.
+    pub fn pivots(&self) -> &[usize] {
+        &self.pivots
+    }
+
+    /// The free variables, which may take any value.
+    pub fn free(&self) -> &[usize] {
+        &self.free
+    }
+
+    pub fn row(&self, i: usize) -> (&[Rational], Rational) {
+        (&self.rows[i], self.rhs[i])
+    }
+
+    /// The solution with the free variables set to `free_values`, in the order of [Self::free].
+    pub fn solve_with(&self, free_values: &[Rational]) -> Vec<Rational> {
+        assert_eq!(free_values.len(), self.free.len());
+        let mut x = vec![Rational::ZERO; self.num_vars()];
+        for (f, value) in self.free.iter().zip(free_values) {
+            x[*f] = *value;
+        }
+        for ((pivot, row), rhs) in self.pivots.iter().zip(&self.rows).zip(&self.rhs) {
+            x[*pivot] = self.free.iter().fold(*rhs, |sum, f| sum - row[*f] * x[*f]);
+        }
+        x
+    }
+
+    /// Each row scaled by the common denominator of its free coefficients and right-hand side, so
+    /// that solutions can be checked in integers: `(scale, coefficients, rhs)` says that `scale`
+    /// times the pivot variable plus the sum of `coefficients[k]` times the `k`th free variable
+    /// equals `rhs`.
+    fn scaled_rows(&self) -> Vec<(i64, Vec<i64>, i64)> {
+        self.rows
+            .iter()
+            .zip(&self.rhs)
+            .map(|(row, rhs)| {
+                let scale = self
+                    .free
+                    .iter()
+                    .fold(rhs.denom(), |l, f| lcm(l, row[*f].denom()));
+                let coefficients = self
+                    .free
+                    .iter()
+                    .map(|f| row[*f].numer() * (scale / row[*f].denom()))
+                    .collect();
+                (scale, coefficients, rhs.numer() * (scale / rhs.denom()))
+            })
+            .collect()
+    }
+
+    /// Every solution in non-negative integers with each `x[j]` at most `bounds[j]`.
+    pub fn non_negative_solutions(&self, bounds: &[i64]) -> NonNegativeSolutions<'_> {
+        assert_eq!(
+            bounds.len(),
+            self.num_vars(),
+            "Expected one bound per variable"
+        );
+        NonNegativeSolutions {
+            rref: self,
+            rows: self.scaled_rows(),
+            bounds: bounds.to_vec(),
+            x: vec![0; bounds.len()],
+            free_values: if self.free.iter().all(|f| bounds[*f] >= 0) {
+                Some(vec![0; self.free.len()])
+            } else {
+                None
+            },
+        }
+    }
+
+    /// The solution in non-negative integers with each `x[j]` at most `bounds[j]` whose variables
+    /// have the smallest sum, or `None` if there is no such solution. Found by branch and bound
+    /// over the free variables, which is much faster than trying every solution when the bounds
+    /// are loose.
+    pub fn min_sum_solution(&self, bounds: &[i64]) -> Option<Vec<i64>> {
+        assert_eq!(
+            bounds.len(),
+            self.num_vars(),
+            "Expected one bound per variable"
+        );
+        if bounds.iter().any(|bound| *bound < 0) {
+            return None;
+        }
+        let mut search = MinSumSearch::new(self, bounds);
+        search.branch(0);
+        search.best.map(|(_, x)| x)
+    }
+}
+
+/// The state of [Rref::min_sum_solution]. The free variables are assigned one at a time in the
+/// order of [Rref::free], and everything is scaled to integers: each row by its `scale` as in
+/// [Rref::scaled_rows], and the sum of all variables by the least common multiple of the scales.
+struct MinSumSearch<'a> {
+    rref: &'a Rref,
+    rows: Vec<(i64, Vec<i64>, i64)>,
+    /// The bound of each free variable.
+    free_bounds: Vec<i64>,
+    /// The scaled bound of each row's pivot variable.
+    pivot_limits: Vec<i64>,
+    /// `rest[r][k]`: the least and greatest sum of the terms of the free variables from the
+    /// `k`th on in row `r`, within their bounds.
+    rest: Vec<Vec<(i64, i64)>>,
+    /// How much each free variable adds to the scaled sum of all variables per unit, counting its
+    /// effect on the pivot variables.
+    weights: Vec<i64>,
+    /// `rest_weight[k]`: the least that the free variables from the `k`th on add to the scaled sum.
+    rest_weight: Vec<i64>,
+    /// The right-hand side of each scaled row minus the terms of the free variables assigned so
+    /// far.
+    residuals: Vec<i64>,
+    values: Vec<i64>,
+    /// The scaled sum of all variables if the free variables not yet assigned were 0.
+    partial: i64,
+    best: Option<(i64, Vec<i64>)>,
+}
+
+impl<'a> MinSumSearch<'a> {
+    fn new(rref: &'a Rref, bounds: &[i64]) -> Self {
+        let rows = rref.scaled_rows();
+        let num_free = rref.free.len();
+        let free_bounds: Vec<i64> = rref.free.iter().map(|f| bounds[*f]).collect();
+        let pivot_limits = rows
+            .iter()
+            .zip(&rref.pivots)
+            .map(|((scale, _, _), pivot)| scale * bounds[*pivot])
+            .collect();
+        let rest = rows
+            .iter()
+            .map(|(_, coefficients, _)| {
+                let mut rest = vec![(0, 0); num_free + 1];
+                for k in (0..num_free).rev() {
+                    let term = coefficients[k] * free_bounds[k];
+                    let (lo, hi) = rest[k + 1];
+                    rest[k] = (lo + term.min(0), hi + term.max(0));
+                }
+                rest
+            })
+            .collect();
+
+        // The sum of all variables is the free variables plus each pivot, `(rhs - terms) / scale`.
+        let sum_scale = rows.iter().fold(1, |l, (scale, _, _)| lcm(l, *scale));
+        let weights: Vec<i64> = (0..num_free)
+            .map(|k| {
+                rows.iter().fold(sum_scale, |w, (scale, coefficients, _)| {
+                    w - sum_scale / scale * coefficients[k]
+                })
+            })
+            .collect();
+        let mut rest_weight = vec![0; num_free + 1];
+        for k in (0..num_free).rev() {
+            rest_weight[k] = rest_weight[k + 1] + (weights[k] * free_bounds[k]).min(0);
+        }
+        let partial = rows
+            .iter()
+            .map(|(scale, _, rhs)| sum_scale / scale * rhs)
+            .sum();
+
+        Self {
+            rref,
+            residuals: rows.iter().map(|(_, _, rhs)| *rhs).collect(),
+            rows,
+            free_bounds,
+            pivot_limits,
+            rest,
+            weights,
+            rest_weight,
+            values: vec![0; num_free],
+            partial,
+            best: None,
+        }
+    }
+
+    /// Try every value of the `k`th free variable that the rows allow, and recurse.
+    fn branch(&mut self, k: usize) {
+        if self
+            .best
+            .as_ref()
+            .is_some_and(|(best, _)| self.partial + self.rest_weight[k] >= *best)
+        {
+            return;
+        }
+        if k == self.values.len() {
+            self.check_solution();
+            return;
+        }
+
+        // Each row needs room for its pivot and for the free variables after this one.
+        let (mut lo, mut hi) = (0, self.free_bounds[k]);
+        for (r, (_, coefficients, _)) in self.rows.iter().enumerate() {
+            let (rest_lo, rest_hi) = self.rest[r][k + 1];
+            let from = self.residuals[r] - self.pivot_limits[r] - rest_hi;
+            let to = self.residuals[r] - rest_lo;
+            let c = coefficients[k];
+            match c.signum() {
+                1 => {
+                    lo = lo.max(div_ceil(from, c));
+                    hi = hi.min(div_floor(to, c));
+                }
+                -1 => {
+                    lo = lo.max(div_ceil(to, c));
+                    hi = hi.min(div_floor(from, c));
+                }
+                _ if from > 0 || to < 0 => return,
+                _ => {}
+            }
+        }
+        if lo > hi {
+            return;
+        }
+
+        // Try the values that add least to the sum first, to find a good bound early.
+        let values: Box<dyn Iterator<Item = i64>> = if self.weights[k] >= 0 {
+            Box::new(lo..=hi)
+        } else {
+            Box::new((lo..=hi).rev())
+        };
+        for value in values {
+            self.assign(k, value);
+            self.branch(k + 1);
+            self.assign(k, 0);
+        }
+    }
+
+    fn assign(&mut self, k: usize, value: i64) {
+        let delta = value - self.values[k];
+        for (residual, (_, coefficients, _)) in self.residuals.iter_mut().zip(&self.rows) {
+            *residual -= coefficients[k] * delta;
+        }
+        self.partial += self.weights[k] * delta;
+        self.values[k] = value;
+    }
+
+    /// Record the current assignment if every pivot comes out a whole number within its bound.
+    fn check_solution(&mut self) {
+        let mut x = vec![0; self.rref.num_vars()];
+        for (f, value) in self.rref.free.iter().zip(&self.values) {
+            x[*f] = *value;
+        }
+        for (((scale, _, _), residual), (pivot, limit)) in self
+            .rows
+            .iter()
+            .zip(&self.residuals)
+            .zip(self.rref.pivots.iter().zip(&self.pivot_limits))
+        {
+            if *residual < 0 || residual > limit || residual % scale != 0 {
+                return;
+            }
+            x[*pivot] = residual / scale;
+        }
+        self.best = Some((self.partial, x));
+    }
+}
+
+/// An iterator over the bounded non-negative integer solutions of an [Rref], which tries every
+/// combination of values of the free variables.
+pub struct NonNegativeSolutions<'a> {
+    rref: &'a Rref,
+    rows: Vec<(i64, Vec<i64>, i64)>,
+    bounds: Vec<i64>,
+    free_values: Option<Vec<i64>>,
+    x: Vec<i64>,
+}
+
+impl NonNegativeSolutions<'_> {
+    fn advance(&mut self) {
+        if let Some(values) = &mut self.free_values {
+            for (value, f) in values.iter_mut().zip(&self.rref.free) {
+                if *value < self.bounds[*f] {
+                    *value += 1;
+                    return;
+                }
+                *value = 0;
+            }
+            self.free_values = None;
+        }
+    }
+
+    /// Fill in `x` from the current values of the free variables, and return whether it is a
+    /// solution.
+    fn solve_current(&mut self) -> bool {
+        let Some(free_values) = &self.free_values else {
+            return false;
+        };
+        for (f, value) in self.rref.free.iter().zip(free_values) {
+            self.x[*f] = *value;
+        }
+        for ((scale, coefficients, rhs), pivot) in self.rows.iter().zip(&self.rref.pivots) {
+            let scaled = coefficients
+                .iter()
+                .zip(free_values)
+                .fold(*rhs, |sum, (c, value)| sum - c * value);
+            if scaled < 0 || scaled % scale != 0 || scaled / scale > self.bounds[*pivot] {
+                return false;
+            }
+            self.x[*pivot] = scaled / scale;
+        }
+        true
+    }
+}
+
+impl Iterator for NonNegativeSolutions<'_> {
+    type Item = Vec<i64>;
+    fn next(&mut self) -> Option<Vec<i64>> {
+        while self.free_values.is_some() {
+            let found = self.solve_current();
+            self.advance();
+            if found {
+                return Some(self.x.clone());
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generate::Rng;
+
+    use super::Rational;
+    use super::Rref;
+
+    fn random_rational(rng: &mut Rng) -> Rational {
+        Rational::new(
+            rng.range(0, 200) as i64 - 100,
+            rng.range(1, 50) as i64 * if rng.chance(50) { 1 } else { -1 },
+        )
+    }
+
+    fn mul(a: &[Vec<i64>], x: &[i64]) -> Vec<i64> {
+        a.iter()
+            .map(|row| row.iter().zip(x).map(|(c, x)| c * x).sum())
+            .collect()
+    }
+
+    #[test]
+    fn rational_is_normalised() {
+        assert_eq!(Rational::new(6, -4), Rational::new(-3, 2));
+        assert_eq!(Rational::new(0, -7), Rational::ZERO);
+        assert_eq!(Rational::new(-6, -3).to_integer(), Some(2));
+        assert_eq!(Rational::new(1, 3).to_integer(), None);
+        assert_eq!(Rational::new(-3, 6).to_string(), "-1/2");
+    }
+
+    #[test]
+    fn rational_field_laws() {
+        let mut rng = Rng::new(0);
+        for _ in 0..1000 {
+            let a = random_rational(&mut rng);
+            let b = random_rational(&mut rng);
+            let c = random_rational(&mut rng);
+            assert_eq!(a + b, b + a);
+            assert_eq!((a + b) - b, a);
+            assert_eq!(a * (b + c), a * b + a * c);
+            if !b.is_zero() {
+                assert_eq!(a * b / b, a);
+            }
+            assert!(a.denom() > 0);
+            assert_eq!(a < b, a - b < Rational::ZERO);
+        }
+    }
+
+    #[test]
+    fn inconsistent_is_none() {
+        assert_eq!(Rref::new(&[vec![1, 1], vec![2, 2]], &[1, 3]), None);
+    }
+
+    #[test]
+    fn underdetermined_has_free_variables() {
+        let rref = Rref::new(&[vec![1, 1, 0], vec![0, 1, 1]], &[3, 5]).unwrap();
+        assert_eq!(rref.pivots(), &[0, 1]);
+        assert_eq!(rref.free(), &[2]);
+        let mut solutions: Vec<Vec<i64>> = rref.non_negative_solutions(&[10, 10, 10]).collect();
+        solutions.sort();
+        assert_eq!(
+            solutions,
+            vec![vec![0, 3, 2], vec![1, 2, 3], vec![2, 1, 4], vec![3, 0, 5]]
+        );
+    }
+
+    #[test]
+    fn random_systems() {
+        for seed in 0..200 {
+            let mut rng = Rng::new(seed);
+            let num_rows = rng.range(1, 5);
+            let num_vars = rng.range(1, 5);
+            let a: Vec<Vec<i64>> = (0..num_rows)
+                .map(|_| (0..num_vars).map(|_| rng.range(0, 6) as i64 - 2).collect())
+                .collect();
+            let x: Vec<i64> = (0..num_vars).map(|_| rng.range(0, 4) as i64).collect();
+            let b = mul(&a, &x);
+            let bounds = vec![4; num_vars];
+
+            let rref = Rref::new(&a, &b).expect("System constructed to be solvable");
+            assert_eq!(rref.rank() + rref.free().len(), num_vars, "seed={seed}");
+            for (i, pivot) in rref.pivots().iter().enumerate() {
+                for r in 0..rref.rank() {
+                    let expected = if r == i {
+                        Rational::ONE
+                    } else {
+                        Rational::ZERO
+                    };
+                    assert_eq!(rref.row(r).0[*pivot], expected, "seed={seed}");
+                }
+            }
+
+            let zeros = vec![Rational::ZERO; rref.free().len()];
+            let x0 = rref.solve_with(&zeros);
+            for (row, rhs) in a.iter().zip(&b) {
+                let lhs = row
+                    .iter()
+                    .zip(&x0)
+                    .fold(Rational::ZERO, |sum, (c, x)| sum + Rational::from(*c) * *x);
+                assert_eq!(lhs, Rational::from(*rhs), "seed={seed}");
+            }
+
+            let mut solutions: Vec<Vec<i64>> = rref.non_negative_solutions(&bounds).collect();
+            solutions.sort();
+            let mut expected: Vec<Vec<i64>> = (0..5_usize.pow(num_vars as u32))
+                .map(|i| {
+                    (0..num_vars)
+                        .map(|v| (i / 5_usize.pow(v as u32) % 5) as i64)
+                        .collect::<Vec<i64>>()
+                })
+                .filter(|candidate| mul(&a, candidate) == b)
+                .collect();
+            expected.sort();
+            assert_eq!(solutions, expected, "seed={seed}");
+            assert!(solutions.contains(&x), "seed={seed}");
+
+            let min_sum = rref.min_sum_solution(&bounds);
+            assert!(
+                min_sum.as_ref().is_some_and(|x| solutions.contains(x)),
+                "seed={seed}"
+            );
+            assert_eq!(
+                min_sum.map(|x| x.iter().sum::<i64>()),
+                solutions.iter().map(|x| x.iter().sum()).min(),
+                "seed={seed}"
+            );
+        }
+    }
+}
@@ -22,17 +22,39 @@ use std::ops::Mul;
 use std::ops::Rem;
 use std::ops::Sub;
 
+pub mod dsu;
+pub mod gf2;
+pub mod graph;
 pub mod iter;
+pub mod linalg;
+pub mod shape;
 
-pub fn gcd(a: usize, b: usize) -> usize {
-    if b == 0 {
+/**
+ * The greatest common divisor of `a` and `b`. For signed integers it may be negative.
+ */
+pub fn gcd<Int>(a: Int, b: Int) -> Int
+where
+    Int: From<u8>,
+    Int: Copy,
+    Int: PartialEq,
+    Int: Rem<Output = Int>,
+{
+    if b == Int::from(0) {
         a
     } else {
         gcd(b, a % b)
     }
 }
 
-pub fn lcm(a: usize, b: usize) -> usize {
+pub fn lcm<Int>(a: Int, b: Int) -> Int
+where
+    Int: From<u8>,
+    Int: Copy,
+    Int: PartialEq,
+    Int: Div<Output = Int>,
+    Int: Mul<Output = Int>,
+    Int: Rem<Output = Int>,
+{
     let gcdab = gcd(a, b);
     (a / gcdab) * b
 }
@@ -0,0 +1,178 @@
+// Solutions to Advent of Code 2025
+// Copyright (C) 2025  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Shapes made of cells on a square grid, such as polyominoes.
+
+use std::ops::Range;
+
+/// A set of cells on a square grid, stored as a bitmap with bit `c` of `rows[r]` set for the cell
+/// in row `r` and column `c`. Cells must lie within the first 64 rows and columns.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Shape {
+    rows: Vec<u64>,
+}
+
+impl Shape {
+    /// The shape of the `(row, column)` cells.
+    pub fn from_cells<I: IntoIterator<Item = (usize, usize)>>(cells: I) -> Self {
+        let mut rows = Vec::new();
+        for (r, c) in cells {
+            assert!(r < 64 && c < 64, "Cell ({r}, {c}) out of range");
+            if rows.len() <= r {
+                rows.resize(r + 1, 0);
+            }
+            rows[r] |= 1 << c;
+        }
+        Self { rows }
+    }
+
+    /// The number of cells.
+    pub fn area(&self) -> usize {
+        self.rows.iter().map(|row| row.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.iter().all(|row| *row == 0)
+    }
+
+    pub fn contains(&self, (r, c): (usize, usize)) -> bool {
+        c < 64 && self.rows.get(r).is_some_and(|row| row & (1 << c) != 0)
+    }
+
+    /// The `(row, column)` of each cell, in row-major order.
+    pub fn cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.rows.iter().enumerate().flat_map(|(r, row)| {
+            (0..64)
+                .filter(move |c| row & (1 << c) != 0)
+                .map(move |c| (r, c))
+        })
+    }
+
+    /// The rows and the columns spanned by the cells, both empty if there are none.
+    pub fn bounding_box(&self) -> (Range<usize>, Range<usize>) {
+        let all = self.rows.iter().fold(0, |all, row| all | row);
+        if all == 0 {
+            return (0..0, 0..0);
+        }
+        let top = self.rows.iter().position(|row| *row != 0).unwrap();
+        let bottom = self.rows.iter().rposition(|row| *row != 0).unwrap() + 1;
+        let left = all.trailing_zeros() as usize;
+        let right = 64 - all.leading_zeros() as usize;
+        (top..bottom, left..right)
+    }
+
+    /// The width and height of the bounding box.
+    pub fn size(&self) -> (usize, usize) {
+        let (rows, cols) = self.bounding_box();
+        (cols.len(), rows.len())
+    }
+
+    /// The same shape moved to touch the top and left edges.
+    pub fn normalised(&self) -> Shape {
+        let (rows, cols) = self.bounding_box();
+        Shape {
+            rows: self.rows[rows]
+                .iter()
+                .map(|row| row >> cols.start)
+                .collect(),
+        }
+    }
+
+    /// The shape rotated a quarter turn clockwise, and normalised.
+    pub fn rotated(&self) -> Shape {
+        let (_, height) = self.size();
+        let shape = self.normalised();
+        Shape::from_cells(shape.cells().map(|(r, c)| (c, height - 1 - r)))
+    }
+
+    /// The shape mirrored left to right, and normalised.
+    pub fn reflected(&self) -> Shape {
+        let (width, _) = self.size();
+        let shape = self.normalised();
+        Shape::from_cells(shape.cells().map(|(r, c)| (r, width - 1 - c)))
+    }
+
+    /// The distinct rotations and reflections of the shape, normalised and sorted.
+    pub fn orientations(&self) -> Vec<Shape> {
+        let mut result = Vec::with_capacity(8);
+        let mut shape = self.normalised();
+        for _ in 0..2 {
+            for _ in 0..4 {
+                shape = shape.rotated();
+                result.push(shape.clone());
+            }
+            shape = shape.reflected();
+        }
+        result.sort();
+        result.dedup();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Shape;
+
+    fn shape(rows: &[&str]) -> Shape {
+        Shape::from_cells(rows.iter().enumerate().flat_map(|(r, row)| {
+            row.chars()
+                .enumerate()
+                .filter(|(_, ch)| *ch == '#')
+                .map(move |(c, _)| (r, c))
+        }))
+    }
+
+    #[test]
+    fn area_and_bounding_box() {
+        let s = shape(&["....", "..#.", ".##.", "...."]);
+        assert_eq!(s.area(), 3);
+        assert_eq!(s.bounding_box(), (1..3, 1..3));
+        assert_eq!(s.size(), (2, 2));
+        assert!(s.contains((1, 2)));
+        assert!(!s.contains((1, 1)));
+        assert_eq!(s.normalised(), shape(&[".#", "##"]));
+        assert_eq!(
+            s.normalised().cells().collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0), (1, 1)]
+        );
+        assert!(shape(&["..."]).is_empty());
+        assert_eq!(shape(&["..."]).size(), (0, 0));
+    }
+
+    #[test]
+    fn rotation_and_reflection() {
+        let l = shape(&["#.", "#.", "##"]);
+        assert_eq!(l.rotated(), shape(&["###", "#.."]));
+        assert_eq!(l.reflected(), shape(&[".#", ".#", "##"]));
+        assert_eq!(l.rotated().rotated().rotated().rotated(), l);
+        assert_eq!(l.reflected().reflected(), l);
+    }
+
+    #[test]
+    fn distinct_orientations() {
+        assert_eq!(shape(&["#"]).orientations().len(), 1);
+        assert_eq!(shape(&["##"]).orientations().len(), 2);
+        assert_eq!(shape(&["##", "##"]).orientations().len(), 1);
+        assert_eq!(shape(&["###", ".#."]).orientations().len(), 4);
+        assert_eq!(shape(&[".##", "##."]).orientations().len(), 4);
+        assert_eq!(shape(&["#.", "#.", "##"]).orientations().len(), 8);
+        assert_eq!(shape(&["###", ".#.", "###"]).orientations().len(), 2);
+        for orientation in shape(&["###", "##.", "##."]).orientations() {
+            assert_eq!(orientation, orientation.normalised());
+            assert_eq!(orientation.area(), 7);
+        }
+    }
+}
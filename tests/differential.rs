@@ -0,0 +1,150 @@
+// Solutions to Advent of Code 2025
+// Copyright (C) 2025  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Differential tests: run each day's default solver against its `reference` variant on many
+//! generated inputs, and shrink any disagreement to a minimal failing input.
+
+use std::panic::catch_unwind;
+
+use adventofcode_2025::common::Solution;
+use adventofcode_2025::days;
+use adventofcode_2025::days::Solver;
+use adventofcode_2025::generate;
+use adventofcode_2025::generate::Rng;
+
+const CASES: u64 = 100;
+
+fn run(solve: Solver, lines: &[String]) -> Option<Solution> {
+    catch_unwind(|| solve(lines)).ok()
+}
+
+/// The input fails if the reference accepts it and the default solver disagrees or panics.
+fn fails(day: u8, lines: &[String]) -> bool {
+    let reference = days::get_variant(day, "reference").unwrap();
+    let solve = days::get_solver(day).unwrap();
+    match run(reference, lines) {
+        Some(expected) => run(solve, lines) != Some(expected),
+        None => false,
+    }
+}
+
+/// Smaller variants of `lines`: with runs of lines removed, with single comma- or
+/// space-separated items removed, and with single numbers made smaller.
+fn shrink_candidates(lines: &[String]) -> Vec<Vec<String>> {
+    let mut candidates = Vec::new();
+
+    let mut chunk = lines.len() / 2;
+    while chunk > 0 {
+        for start in (0..lines.len()).step_by(chunk) {
+            let mut candidate = lines.to_vec();
+            candidate.drain(start..(start + chunk).min(lines.len()));
+            candidates.push(candidate);
+        }
+        chunk /= 2;
+    }
+
+    for (i, line) in lines.iter().enumerate() {
+        for sep in [',', ' '] {
+            let items: Vec<&str> = line.split(sep).collect();
+            if items.len() > 1 {
+                for skip in 0..items.len() {
+                    let mut candidate = lines.to_vec();
+                    candidate[i] = items
+                        .iter()
+                        .enumerate()
+                        .filter(|(j, _)| *j != skip)
+                        .map(|(_, item)| *item)
+                        .collect::<Vec<_>>()
+                        .join(&sep.to_string());
+                    candidates.push(candidate);
+                }
+            }
+        }
+
+        let bytes = line.as_bytes();
+        let mut start = 0;
+        while start < bytes.len() {
+            let end = start
+                + bytes[start..]
+                    .iter()
+                    .take_while(|b| b.is_ascii_digit())
+                    .count();
+            if end > start {
+                let n: u64 = line[start..end].parse().unwrap_or(0);
+                for smaller in [0, n / 2, n.saturating_sub(1)] {
+                    if smaller < n {
+                        let mut candidate = lines.to_vec();
+                        candidate[i] = format!("{}{smaller}{}", &line[..start], &line[end..]);
+                        candidates.push(candidate);
+                    }
+                }
+                start = end;
+            } else {
+                start += 1;
+            }
+        }
+    }
+
+    candidates
+}
+
+fn shrink(day: u8, mut lines: Vec<String>) -> Vec<String> {
+    while let Some(smaller) = shrink_candidates(&lines)
+        .into_iter()
+        .find(|candidate| fails(day, candidate))
+    {
+        lines = smaller;
+    }
+    lines
+}
+
+fn check_day(day: u8) {
+    let generate = generate::get_generator(day).unwrap();
+    for seed in 0..CASES {
+        let mut rng = Rng::new(seed);
+        let size = 2 + usize::try_from(seed).unwrap() / 5;
+        let lines = generate(&mut rng, size);
+        if fails(day, &lines) {
+            let minimal = shrink(day, lines);
+            let reference = days::get_variant(day, "reference").unwrap();
+            let solve = days::get_solver(day).unwrap();
+            panic!(
+                "Day {day} disagrees with reference on generated input (seed {seed}), shrunk to:\n{}\nreference: {:?}\ndefault: {:?}",
+                minimal.join("\n"),
+                run(reference, &minimal),
+                run(solve, &minimal),
+            );
+        }
+    }
+}
+
+macro_rules! differential_test {
+    ($($(#[$attr:meta])* $name: ident),* $(,)?) => {
+        $(
+            $(#[$attr])*
+            #[test]
+            fn $name() {
+                let day_name = stringify!($name);
+                let day_num: u8 = day_name[3..].parse().unwrap();
+                check_day(day_num);
+            }
+        )*
+    };
+}
+
+differential_test!(
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12,
+);
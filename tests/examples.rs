@@ -0,0 +1,157 @@
+// Solutions to Advent of Code 2025
+// Copyright (C) 2025  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! The examples from the puzzle descriptions, which unlike the puzzle inputs are public.
+
+use adventofcode_2025::common::check_solution;
+use adventofcode_2025::common::AocError;
+use adventofcode_2025::days;
+
+fn test_example(day: u8, input: &str, correct_a: &str, correct_b: &str) -> Result<(), AocError> {
+    let lines: Vec<String> = input.lines().map(&str::to_string).collect();
+    let solution = days::try_solve(day, days::DEFAULT_VARIANT, &lines)?;
+    check_solution(day, &solution, correct_a, correct_b)
+}
+
+macro_rules! test_example {
+    ($(#[$attr:meta])* $name: ident, $sol_a: literal, $sol_b: literal) => {
+        $(#[$attr])*
+        #[test]
+        fn $name() -> Result<(), AocError> {
+            let day_name = stringify!($name);
+            let day_num: u8 = day_name[3..5].parse().unwrap();
+            test_example(
+                day_num,
+                include_str!(concat!("examples/", stringify!($name), ".in")),
+                $sol_a,
+                $sol_b,
+            )
+        }
+    };
+}
+
+test_example!(day01, "3", "6");
+test_example!(day02, "1227775554", "4174379265");
+test_example!(day03, "357", "3121910778619");
+test_example!(day04, "13", "43");
+test_example!(day05, "3", "14");
+test_example!(day06, "4277556", "3263827");
+test_example!(day07, "21", "40");
+// The example makes 10 connections instead of 1000, so part A differs from the puzzle text.
+test_example!(day08, "20", "25272");
+test_example!(day09, "50", "24");
+test_example!(day10, "7", "33");
+test_example!(day11, "5", "0");
+// Part B has its own example, which has no path from "you".
+test_example!(day11b, "0", "2");
+test_example!(day12, "2", "");
+// Mid-size regions too tight for the area bounds, which the search must decide by itself.
+test_example!(day12b, "2", "");
+
+fn explain(day: u8, input: &str) -> Vec<String> {
+    let explain = days::get_explainer(day).unwrap();
+    let lines: Vec<String> = input.lines().map(&str::to_string).collect();
+    explain(&lines)
+}
+
+#[test]
+fn explain_day03() {
+    assert_eq!(
+        explain(3, include_str!("examples/day03.in")),
+        vec![
+            "Bank 1: A picks 98, B picks 987654321111",
+            "Bank 2: A picks 89, B picks 811111111119",
+            "Bank 3: A picks 78, B picks 434234234278",
+            "Bank 4: A picks 92, B picks 888911112111",
+        ]
+    );
+}
+
+#[test]
+fn explain_day05() {
+    let explanation = explain(5, include_str!("examples/day05.in"));
+    assert_eq!(explanation[0], "Fresh IDs: [5, 11, 17]");
+    assert_eq!(explanation[2..], ["3-5: 3 IDs", "10-20: 11 IDs"]);
+}
+
+#[test]
+fn explain_day09() {
+    let explanation = explain(9, include_str!("examples/day09.in"));
+    assert_eq!(explanation[1], "B: corners 9,5 and 2,3, area 24");
+}
+
+#[test]
+fn explain_day11() {
+    assert_eq!(
+        explain(11, include_str!("examples/day11.in")),
+        vec![
+            "you -> bbb -> ddd -> ggg -> out",
+            "you -> bbb -> eee -> out",
+            "you -> ccc -> ddd -> ggg -> out",
+            "you -> ccc -> eee -> out",
+            "you -> ccc -> fff -> out",
+        ]
+    );
+}
+
+/// A chain of 20 diamonds has over a million paths, too many to list.
+#[test]
+fn explain_day11_many_paths() {
+    let mut input = String::from("you: a0 b0\n");
+    for i in 0..20 {
+        let next = if i == 19 {
+            "out".to_string()
+        } else {
+            format!("n{}", i + 1)
+        };
+        input += &format!("a{i}: {next}\nb{i}: {next}\n");
+        if i < 19 {
+            input += &format!("{next}: a{} b{}\n", i + 1, i + 1);
+        }
+    }
+    let explanation = explain(11, &input);
+    assert_eq!(explanation.len(), 101);
+    assert_eq!(
+        explanation[100],
+        format!("... and {} more paths", (1 << 20) - 100)
+    );
+}
+
+/// Every example region is too tight for the area bounds to decide.
+#[test]
+fn explain_day12() {
+    assert_eq!(
+        explain(12, include_str!("examples/day12.in")),
+        vec![
+            "Region 1 (4x4): fits: packed by search",
+            "Region 2 (12x5): fits: packed by search",
+            "Region 3 (12x5): does not fit: no packing found by search",
+        ]
+    );
+}
+
+/// The search must also decide regions larger than those in the puzzle text.
+#[test]
+fn explain_day12b() {
+    assert_eq!(
+        explain(12, include_str!("examples/day12b.in")),
+        vec![
+            "Region 1 (10x10): fits: packed by search",
+            "Region 2 (9x9): fits: packed by search",
+            "Region 3 (8x8): does not fit: no packing found by search",
+        ]
+    );
+}
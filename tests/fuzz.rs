@@ -0,0 +1,130 @@
+// Solutions to Advent of Code 2025
+// Copyright (C) 2025  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Fuzz tests for each day: mutate generated inputs and require that solving either succeeds or
+//! returns an error, without panicking or hanging, so that input the parse step accepts is also
+//! safe to solve. Set `FUZZ_ITERATIONS` to fuzz for longer, for example
+//! `FUZZ_ITERATIONS=1000000 cargo test --release --test fuzz`.
+
+use std::panic::catch_unwind;
+use std::sync::mpsc;
+use std::sync::mpsc::RecvTimeoutError;
+use std::time::Duration;
+
+use adventofcode_2025::days;
+use adventofcode_2025::generate;
+use adventofcode_2025::generate::Rng;
+
+const DEFAULT_ITERATIONS: u64 = 2000;
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Characters to insert: the puzzles' punctuation, whitespace and multi-byte UTF-8.
+const ALPHABET: &[char] = &[
+    '0', '1', '9', '-', ',', ':', ' ', '\t', 'x', '#', '.', '@', '^', 'S', '+', '*', '[', ']', '(',
+    ')', '{', '}', 'L', 'R', 'é', '€', '😀',
+];
+
+/// A random char boundary in `line`, including its end.
+fn boundary(rng: &mut Rng, line: &str) -> usize {
+    let boundaries: Vec<usize> = line
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(line.len()))
+        .collect();
+    boundaries[rng.range(0, boundaries.len() - 1)]
+}
+
+fn mutate(rng: &mut Rng, lines: &mut Vec<String>) {
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    let i = rng.range(0, lines.len() - 1);
+    let at = boundary(rng, &lines[i]);
+    match rng.range(0, 8) {
+        0 => lines[i].insert(at, ALPHABET[rng.range(0, ALPHABET.len() - 1)]),
+        1 => {
+            if at < lines[i].len() {
+                lines[i].remove(at);
+            }
+        }
+        2 => lines[i].truncate(at),
+        3 => lines[i].insert_str(at, "99999999999999999999999"),
+        4 => lines[i].insert_str(at, "-1"),
+        5 => lines[i].insert_str(at, &i64::MIN.to_string()),
+        6 => {
+            lines.remove(i);
+        }
+        7 => lines.insert(i, lines[i].clone()),
+        _ => lines.insert(i, String::new()),
+    }
+}
+
+fn fuzz_day(day: u8) {
+    let iterations: u64 = std::env::var("FUZZ_ITERATIONS")
+        .map(|n| n.parse().expect("Invalid FUZZ_ITERATIONS"))
+        .unwrap_or(DEFAULT_ITERATIONS);
+    let generate = generate::get_generator(day).unwrap();
+
+    let (tx, rx) = mpsc::channel::<Vec<String>>();
+    let fuzzer = std::thread::spawn(move || -> Result<(), Vec<String>> {
+        for seed in 0..iterations {
+            let mut rng = Rng::new(seed);
+            let mut lines = if seed == 0 {
+                Vec::new()
+            } else {
+                let size = rng.range(1, 10);
+                generate(&mut rng, size)
+            };
+            for _ in 0..rng.range(0, 4) {
+                mutate(&mut rng, &mut lines);
+            }
+            tx.send(lines.clone()).unwrap();
+            if catch_unwind(|| days::try_solve(day, days::DEFAULT_VARIANT, &lines)).is_err() {
+                return Err(lines);
+            }
+        }
+        Ok(())
+    });
+
+    let mut last = Vec::new();
+    loop {
+        match rx.recv_timeout(TIMEOUT) {
+            Ok(lines) => last = lines,
+            Err(RecvTimeoutError::Disconnected) => break,
+            Err(RecvTimeoutError::Timeout) => {
+                panic!("Day {day} did not finish on input:\n{}", last.join("\n"))
+            }
+        }
+    }
+    if let Err(lines) = fuzzer.join().unwrap() {
+        panic!("Day {day} panicked on input:\n{}", lines.join("\n"));
+    }
+}
+
+macro_rules! fuzz_test {
+    ($($name: ident),* $(,)?) => {
+        $(
+            #[test]
+            fn $name() {
+                let day_name = stringify!($name);
+                let day_num: u8 = day_name[3..].parse().unwrap();
+                fuzz_day(day_num);
+            }
+        )*
+    };
+}
+
+fuzz_test!(day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12);
@@ -14,34 +14,23 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use adventofcode_2025::common::check_solution;
 use adventofcode_2025::common::day_input_filename;
 use adventofcode_2025::common::get_file_lines;
+use adventofcode_2025::common::AocError;
 use adventofcode_2025::days;
 
-fn test_day(day: u8, correct_a: &str, correct_b: &str) -> Result<(), std::io::Error> {
-    let solve = days::get_solver(day).unwrap();
+fn test_day(day: u8, correct_a: &str, correct_b: &str) -> Result<(), AocError> {
     let input_lines = get_file_lines(&day_input_filename(day))?;
-    let (solution_a, solution_b) = solve(&input_lines);
-    assert_eq!(
-        solution_a.as_str(),
-        correct_a,
-        "Incorrect solution for day {}a",
-        day
-    );
-    assert_eq!(
-        solution_b.as_str(),
-        correct_b,
-        "Incorrect solution for day {}b",
-        day
-    );
-
-    Ok(())
+    let solution = days::try_solve(day, days::DEFAULT_VARIANT, &input_lines)?;
+    check_solution(day, &solution, correct_a, correct_b)
 }
 
 macro_rules! test_day {
     ($name: ident, $sol_a: literal, $sol_b: literal) => {
         #[test]
-        fn $name() -> Result<(), std::io::Error> {
+        #[cfg_attr(not(inputs), ignore = "requires the private inputs submodule")]
+        fn $name() -> Result<(), AocError> {
             let day_name = stringify!($name);
             let day_num: u8 = day_name[3..].parse().unwrap();
             test_day(day_num, $sol_a, $sol_b)